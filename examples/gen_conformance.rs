@@ -0,0 +1,15 @@
+//writes the generated conformance cases in the gktest.csv format
+//cargo run --example gen_conformance [output.csv]
+
+use rustunicodetests::*;
+
+fn main() -> std::io::Result<()> {
+    let csv = hgk_conformance_csv(&hgk_conformance_cases());
+    match std::env::args().nth(1) {
+        Some(path) => std::fs::write(path, csv),
+        None => {
+            print!("{}", csv);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,179 @@
+/*
+Generates GREEK_BASIC and GREEK_EXTENDED from UnicodeData.txt into src/tables_generated.rs.
+The tests in tables.rs check the generated tables against the hand tables.
+
+cargo run --example gen_tables [UnicodeData.txt] [output.rs]
+
+By default this reads unicode/UnicodeData-greek.txt and writes src/tables_generated.rs.  To
+update to a newer unicode version, run it on the new UnicodeData.txt, then cargo test: any
+differences from the hand tables are listed.
+*/
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+
+const DEFAULT_UNICODE_DATA: &str = "unicode/UnicodeData-greek.txt";
+const DEFAULT_OUTPUT: &str = "src/tables_generated.rs";
+
+//combining marks, in the order the HGK_ diacritics are written
+const MARKS: &[(u32, &str)] = &[
+    (0x0314, "HGK_ROUGH"),
+    (0x0313, "HGK_SMOOTH"),
+    (0x0301, "HGK_ACUTE"),
+    (0x0300, "HGK_GRAVE"),
+    (0x0342, "HGK_CIRCUMFLEX"),
+    (0x0304, "HGK_MACRON"),
+    (0x0306, "HGK_BREVE"),
+    (0x0345, "HGK_IOTA_SUBSCRIPT"),
+    (0x0308, "HGK_DIAERESIS"),
+    (0x0323, "HGK_UNDERDOT")
+];
+
+//unicode letter names in sort order, and the suffix of their HGK_*_SORT constant
+const LETTERS: &[(&str, &str)] = &[
+    ("ALPHA", "ALPHA"), ("BETA", "BETA"), ("GAMMA", "GAMMA"), ("DELTA", "DELTA"), ("EPSILON", "EPSILON"),
+    ("DIGAMMA", "DIGAMMA"), ("ZETA", "ZETA"), ("ETA", "ETA"), ("THETA", "THETA"), ("IOTA", "IOTA"),
+    ("KAPPA", "KAPPA"), ("LAMDA", "LAMBDA"), ("MU", "MU"), ("NU", "NU"), ("XI", "XI"),
+    ("OMICRON", "OMICRON"), ("PI", "PI"), ("SAN", "SAN"), ("KOPPA", "KOPPA"), ("RHO", "RHO"),
+    ("SIGMA", "SIGMA"), ("TAU", "TAU"), ("UPSILON", "UPSILON"), ("PHI", "PHI"), ("CHI", "CHI"),
+    ("PSI", "PSI"), ("OMEGA", "OMEGA"),
+    //numerals
+    ("STIGMA", "DIGAMMA"), ("ARCHAIC KOPPA", "KOPPA"), ("SAMPI", "SAMPI"), ("ARCHAIC SAMPI", "SAMPI")
+];
+
+const VOWELS: &[&str] = &["ALPHA", "EPSILON", "ETA", "IOTA", "OMICRON", "UPSILON", "OMEGA"];
+
+//letters outside the 24 letter alphabet which take diacritics or sort with it
+const ARCHAIC: &[&str] = &["DIGAMMA", "SAN", "KOPPA", "ARCHAIC KOPPA"];
+
+struct Entry {
+    name: String,
+    decomposition: Vec<u32> //canonical only
+}
+
+fn parse(data:&str) -> HashMap<u32, Entry> {
+    let mut entries = HashMap::new();
+    for line in data.lines() {
+        let fields:Vec<&str> = line.split(';').collect();
+        if fields.len() < 6 {
+            continue;
+        }
+        let Ok(cp) = u32::from_str_radix(fields[0], 16) else { continue };
+        let decomposition = if fields[5].starts_with('<') {
+            Vec::new()
+        }
+        else {
+            fields[5].split_whitespace().filter_map(|d| u32::from_str_radix(d, 16).ok()).collect()
+        };
+        entries.insert(cp, Entry { name: fields[1].to_string(), decomposition });
+    }
+    entries
+}
+
+fn full_decomposition(cp:u32, entries:&HashMap<u32, Entry>) -> Vec<u32> {
+    match entries.get(&cp) {
+        Some(e) if !e.decomposition.is_empty() => e.decomposition.iter().flat_map(|d| full_decomposition(*d, entries)).collect(),
+        _ => vec![cp]
+    }
+}
+
+//the sort name of a letter, e.g. GREEK SMALL LETTER FINAL SIGMA -> SIGMA
+fn letter_name(cp:u32, entries:&HashMap<u32, Entry>) -> Option<&'static str> {
+    let name = &entries.get(&cp)?.name;
+    if name == "GREEK UPSILON WITH HOOK SYMBOL" {
+        return Some("UPSILON");
+    }
+    let rest = name.strip_prefix("GREEK ")?;
+    let rest = rest.strip_prefix("CAPITAL ").or_else(|| rest.strip_prefix("SMALL ")).unwrap_or(rest);
+    let rest = rest.strip_prefix("LETTER ")?;
+    let rest = rest.strip_prefix("FINAL ").unwrap_or(rest);
+    let letter = rest.split(" WITH ").next()?;
+    LETTERS.iter().find(|l| l.0 == letter).map(|l| l.0)
+}
+
+fn sort_const(letter:Option<&str>) -> String {
+    match letter.and_then(|n| LETTERS.iter().find(|l| l.0 == n)) {
+        Some(l) => format!("HGK_{}_SORT", l.1),
+        None => String::from("HGK_NO_SORT")
+    }
+}
+
+fn takes_diacritics(cp:u32, letter:&str) -> bool {
+    VOWELS.contains(&letter) || ARCHAIC.contains(&letter) || letter == "RHO" || cp == 0x03D2
+}
+
+//(base letter, diacritics), NOT_ACCENTABLE_CHAR, or None if cp is not a letter
+enum Derived {
+    Letter(u32, Vec<&'static str>),
+    NotAccentable,
+    None
+}
+
+fn derive(cp:u32, entries:&HashMap<u32, Entry>) -> Derived {
+    let decomposed = full_decomposition(cp, entries);
+    let base = decomposed[0];
+    let Some(letter) = letter_name(base, entries) else { return Derived::None };
+
+    if base != cp {
+        let marks:Option<Vec<&'static str>> = decomposed[1..].iter().map(|m| MARKS.iter().find(|k| k.0 == *m).map(|k| k.1)).collect();
+        return match marks {
+            //a singleton decomposition to a bare letter, e.g. prosgegrammeni, is not that letter
+            Some(marks) if !marks.is_empty() && takes_diacritics(base, letter) => {
+                let ordered = MARKS.iter().map(|k| k.1).filter(|k| marks.contains(k)).collect();
+                Derived::Letter(base, ordered)
+            },
+            _ => Derived::None
+        };
+    }
+
+    if takes_diacritics(cp, letter) && letter != "RHO" {
+        Derived::Letter(cp, Vec::new())
+    }
+    else {
+        Derived::NotAccentable
+    }
+}
+
+fn diacritics_str(d:&[&str]) -> String {
+    if d.is_empty() { String::from("HGK_NO_DIACRITICS") } else { d.join(" | ") }
+}
+
+fn generate(entries:&HashMap<u32, Entry>) -> String {
+    let mut out = String::new();
+    out.push_str("//generated by examples/gen_tables.rs from UnicodeData.txt, do not edit\n\n");
+
+    out.push_str("pub(crate) const GENERATED_GREEK_BASIC: &[(char, u32, u32)] = &[\n");
+    for cp in 0x0370..=0x03FF {
+        let (letter, diacritics, sort) = match derive(cp, entries) {
+            Derived::Letter(base, d) => (format!("'\\u{{{:04X}}}'", base), diacritics_str(&d), sort_const(letter_name(base, entries))),
+            Derived::NotAccentable => (String::from("NOT_ACCENTABLE_CHAR"), String::from("NOCHAR"), sort_const(letter_name(cp, entries))),
+            Derived::None => (String::from("'\\u{0000}'"), String::from("HGK_NO_DIACRITICS"), String::from("HGK_NO_SORT"))
+        };
+        let _ = writeln!(out, "    /* {:04X} */ ( {}, {}, {} ),", cp, letter, diacritics, sort);
+    }
+    out.push_str("];\n\n");
+
+    out.push_str("pub(crate) const GENERATED_GREEK_EXTENDED: &[(char, u32)] = &[\n");
+    for cp in 0x1F00..=0x1FFF {
+        let (letter, diacritics) = match derive(cp, entries) {
+            Derived::Letter(base, d) => (format!("'\\u{{{:04X}}}'", base), diacritics_str(&d)),
+            _ => (String::from("'\\u{0000}'"), String::from("HGK_NO_DIACRITICS"))
+        };
+        let _ = writeln!(out, "    /* {:04X} */ ( {}, {} ),", cp, letter, diacritics);
+    }
+    out.push_str("];\n");
+    out
+}
+
+fn main() -> std::io::Result<()> {
+    let mut args = env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| String::from(DEFAULT_UNICODE_DATA));
+    let out = args.next().unwrap_or_else(|| String::from(DEFAULT_OUTPUT));
+
+    let data = fs::read_to_string(&path)?;
+    fs::write(&out, generate(&parse(&data)))?;
+    println!("wrote {}", out);
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::*;
+
+/// One row of gktest.csv: toggling diacritic on start in unicode_mode should give result.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HgkConformanceCase {
+    pub start: String,
+    pub diacritic: Diacritics,
+    pub only_on: bool,
+    pub unicode_mode: HgkUnicodeMode,
+    pub result: String
+}
+
+pub const HGK_CONFORMANCE_MODES: [HgkUnicodeMode; 3] = [HgkUnicodeMode::Precomposed, HgkUnicodeMode::CombiningOnly, HgkUnicodeMode::PrecomposedPUA];
+
+pub const HGK_CONFORMANCE_CSV_HEADER: &str = "start,diacritic,toggleoff_mode,unicodemode,result,isequal,comment";
+
+//the names used in gktest.csv
+fn diacritic_csv_name(d:Diacritics) -> &'static str {
+    match d.bits() {
+        HGK_ROUGH => "rough",
+        HGK_SMOOTH => "smooth",
+        HGK_ACUTE => "acute",
+        HGK_GRAVE => "grave",
+        HGK_CIRCUMFLEX => "circumflex",
+        HGK_MACRON => "macron",
+        HGK_BREVE => "breve",
+        HGK_IOTA_SUBSCRIPT => "iotasub",
+        HGK_DIAERESIS => "diaeresis",
+        HGK_UNDERDOT => "underdot",
+        _ => "none"
+    }
+}
+
+fn mode_csv_name(mode:HgkUnicodeMode) -> &'static str {
+    match mode {
+        HgkUnicodeMode::Precomposed => "Precomposed",
+        HgkUnicodeMode::CombiningOnly => "CombiningOnly",
+        HgkUnicodeMode::PrecomposedPUA => "PrecomposedPUA",
+        HgkUnicodeMode::CombiningNFD => "CombiningNFD"
+    }
+}
+
+/// Every lowercase and capital letter of the basic Greek alphabet, including final sigma.
+pub fn hgk_conformance_letters() -> impl Iterator<Item = char> {
+    ('α'..='ω').chain('Α'..='Ω').filter(|c| *c != '\u{03A2}') //unassigned
+}
+
+/// Every diacritic combination on letter in which each diacritic is legal and none clash.
+pub fn hgk_legal_combinations(letter:char) -> Vec<Diacritics> {
+    let l = HGKLetter::new(letter, Diacritics::NONE);
+    let legal = Diacritics::ALL.iter().filter(|d| l.is_legal(*d)).collect::<Diacritics>();
+
+    (0..=Diacritics::ALL.bits())
+        .filter_map(Diacritics::from_bits)
+        .filter(|c| legal.contains(*c) && c.iter().all(|d| !c.intersects(hgk_clashing_diacritics(d))))
+        .collect()
+}
+
+/// For every letter and legal combination, turning each of its diacritics on (onlyon)
+/// and off (toggleoff), rendered in each unicode mode.
+pub fn hgk_conformance_cases() -> Vec<HgkConformanceCase> {
+    let mut cases = Vec::new();
+    for letter in hgk_conformance_letters() {
+        for combination in hgk_legal_combinations(letter) {
+            for d in combination {
+                let with = HGKLetter::new(letter, combination);
+                let without = HGKLetter::new(letter, combination - d);
+                for unicode_mode in HGK_CONFORMANCE_MODES {
+                    cases.push(HgkConformanceCase { start: without.to_string(unicode_mode), diacritic: d, only_on: true, unicode_mode, result: with.to_string(unicode_mode) });
+                    cases.push(HgkConformanceCase { start: with.to_string(unicode_mode), diacritic: d, only_on: false, unicode_mode, result: without.to_string(unicode_mode) });
+                }
+            }
+        }
+    }
+    cases
+}
+
+/// Space separated utf16 hex, as in gktest.csv, e.g. "03B1 0304".
+pub fn hgk_to_hex(s:&str) -> String {
+    let mut res = String::new();
+    for (i, u) in s.encode_utf16().enumerate() {
+        if i > 0 {
+            res.push(' ');
+        }
+        let _ = write!(res, "{:04X}", u);
+    }
+    res
+}
+
+/// Write cases in the gktest.csv format, header included.
+pub fn hgk_conformance_csv(cases:&[HgkConformanceCase]) -> String {
+    let mut res = String::from(HGK_CONFORMANCE_CSV_HEADER);
+    res.push('\n');
+    for c in cases {
+        let _ = writeln!(res, "{},{},{},{},{},equal,", hgk_to_hex(&c.start), diacritic_csv_name(c.diacritic), if c.only_on { "onlyon" } else { "toggleoff" }, mode_csv_name(c.unicode_mode), hgk_to_hex(&c.result));
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conformance_round_trip() {
+        for letter in hgk_conformance_letters() {
+            for combination in hgk_legal_combinations(letter) {
+                let l = HGKLetter::new(letter, combination);
+                for unicode_mode in HGK_CONFORMANCE_MODES {
+                    let s = l.to_string(unicode_mode);
+                    assert_eq!(s.gkletters().collect::<Vec<HGKLetter>>(), vec![HGKLetter::new(letter, combination)], "{} {:?}", hgk_to_hex(&s), unicode_mode);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn combining_nfd() {
+        let mut reordered = 0;
+        for letter in hgk_conformance_letters() {
+            for combination in hgk_legal_combinations(letter) {
+                let l = HGKLetter::new(letter, combination);
+                let nfd = l.to_string(HgkUnicodeMode::CombiningNFD);
+                assert_eq!(nfd, l.to_string(HgkUnicodeMode::Precomposed).nfd().collect::<String>(), "{:?}", l);
+                assert_eq!(nfd, l.to_string(HgkUnicodeMode::CombiningOnly).nfd().collect::<String>(), "{:?}", l);
+                assert_eq!(nfd.gkletters().collect::<Vec<HGKLetter>>(), vec![l]);
+                if nfd != HGKLetter::new(letter, combination).to_string(HgkUnicodeMode::CombiningOnly) {
+                    reordered += 1;
+                }
+            }
+        }
+        assert!(reordered > 0);
+        assert_eq!(HGKLetter::new('α', Diacritics::SMOOTH | Diacritics::UNDERDOT).to_string(HgkUnicodeMode::CombiningNFD), "α\u{0323}\u{0313}");
+    }
+
+    #[test]
+    fn conformance_cases() {
+        assert_eq!(hgk_legal_combinations('β'), vec![Diacritics::NONE, Diacritics::UNDERDOT]);
+        assert!(hgk_legal_combinations('α').contains(&(Diacritics::MACRON | Diacritics::ROUGH | Diacritics::ACUTE | Diacritics::IOTA_SUBSCRIPT)));
+        assert!(!hgk_legal_combinations('α').contains(&(Diacritics::ACUTE | Diacritics::GRAVE)));
+
+        let cases = hgk_conformance_cases();
+        for c in &cases {
+            assert_eq!(hgk_toggle_diacritic_str(&c.start, c.diacritic, c.only_on, c.unicode_mode), c.result, "{:?}", c);
+        }
+
+        let csv = hgk_conformance_csv(&cases);
+        assert_eq!(csv.lines().count(), cases.len() + 1);
+        assert!(csv.starts_with(HGK_CONFORMANCE_CSV_HEADER));
+        assert!(csv.contains("\n1FB1,acute,onlyon,PrecomposedPUA,EB00,equal,\n"));
+    }
+}
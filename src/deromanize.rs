@@ -0,0 +1,181 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::*;
+
+//a latin base character and the combining marks after it
+struct Unit {
+    base: char,
+    upper: bool,
+    marks: Vec<char>
+}
+
+fn latin_vowel(c:char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+fn units(word:&str) -> Vec<Unit> {
+    let mut res:Vec<Unit> = Vec::new();
+    for c in word.nfd() {
+        match res.last_mut() {
+            Some(u) if ('\u{0300}'..='\u{036F}').contains(&c) => u.marks.push(c),
+            _ => res.push(Unit { base: c.to_ascii_lowercase(), upper: c.is_uppercase(), marks: Vec::new() })
+        }
+    }
+    res
+}
+
+fn greek_case(c:char, upper:bool) -> char {
+    if upper { c.to_uppercase().next().unwrap_or(c) } else { c }
+}
+
+fn vowel_letter(u:&Unit, scheme:HgkRomanization) -> HGKLetter {
+    let long = u.marks.contains(&'\u{0304}') || u.marks.contains(&'\u{0302}');
+    let letter = match u.base {
+        'a' => 'α',
+        'e' => if long { 'η' } else { 'ε' },
+        'i' => 'ι',
+        'o' => if long { 'ω' } else { 'ο' },
+        _ => 'υ'
+    };
+
+    let mut diacritics = Diacritics::NONE;
+    for m in &u.marks {
+        diacritics |= match m {
+            '\u{0304}' if matches!(u.base, 'a' | 'i' | 'u' | 'y') => Diacritics::MACRON,
+            '\u{0306}' => Diacritics::BREVE,
+            '\u{0308}' => Diacritics::DIAERESIS,
+            '\u{0328}' => Diacritics::IOTA_SUBSCRIPT,
+            '\u{0301}' if scheme == HgkRomanization::Scientific => Diacritics::ACUTE,
+            '\u{0300}' if scheme == HgkRomanization::Scientific => Diacritics::GRAVE,
+            '\u{0302}' if scheme == HgkRomanization::Scientific => Diacritics::CIRCUMFLEX,
+            '\u{0323}' if scheme == HgkRomanization::Scientific => Diacritics::UNDERDOT,
+            _ => Diacritics::NONE
+        };
+    }
+    HGKLetter::new(letter, diacritics)
+}
+
+fn parse_word(word:&str, scheme:HgkRomanization, res:&mut Vec<HGKLetter>) {
+    let units = units(word);
+    let start = res.len();
+    let mut rough = false;  //an h waiting for its vowel
+    let mut rough_upper = false;
+    let mut i = 0;
+
+    while i < units.len() {
+        let u = &units[i];
+        let next = units.get(i + 1).map(|n| n.base);
+        let upper = u.upper || (rough_upper && latin_vowel(u.base));
+
+        let (letter, used) = match (u.base, next) {
+            ('t', Some('h')) => ('θ', 2),
+            ('p', Some('h')) => ('φ', 2),
+            ('c', Some('h')) => ('χ', 2),
+            ('p', Some('s')) => ('ψ', 2),
+            ('r', Some('h')) => ('ρ', 2),
+            ('h', _) => {
+                rough = true;
+                rough_upper = u.upper;
+                i += 1;
+                continue;
+            },
+            //SBL writes γ before a velar as n
+            ('n', Some('g' | 'k' | 'x' | 'c')) if scheme != HgkRomanization::Scientific
+                && (next != Some('c') || units.get(i + 2).is_some_and(|n| n.base == 'h')) => ('γ', 1),
+            (c, _) if latin_vowel(c) => {
+                let mut l = vowel_letter(u, scheme);
+                l.letter = greek_case(l.letter, upper);
+                if rough {
+                    l.diacritics |= Diacritics::ROUGH;
+                    rough = false;
+                    rough_upper = false;
+                }
+                res.push(l);
+                i += 1;
+                continue;
+            },
+            ('b', _) => ('β', 1),
+            ('g', _) => ('γ', 1),
+            ('d', _) => ('δ', 1),
+            ('z', _) => ('ζ', 1),
+            ('k', _) => ('κ', 1),
+            ('l', _) => ('λ', 1),
+            ('m', _) => ('μ', 1),
+            ('n', _) => ('ν', 1),
+            ('x', _) => ('ξ', 1),
+            ('p', _) => ('π', 1),
+            ('r', _) => ('ρ', 1),
+            ('s', _) => if i + 1 == units.len() { ('ς', 1) } else { ('σ', 1) },
+            ('t', _) => ('τ', 1),
+            ('w', _) => ('ϝ', 1),
+            (c, _) => (c, 1)
+        };
+        let diacritics = if u.base == 'r' && used == 2 { Diacritics::ROUGH } else { Diacritics::NONE };
+        res.push(HGKLetter::new(greek_case(letter, upper), diacritics));
+        i += used;
+    }
+
+    //breathing goes on an initial vowel, or on the second vowel of an initial diphthong.
+    //An accent on the first vowel means it is not a diphthong, e.g. Ἅιδης
+    let word = &mut res[start..];
+    if word.first().is_some_and(|l| l.letter.is_greek_vowel()) {
+        let accents = Diacritics::ACUTE | Diacritics::GRAVE | Diacritics::CIRCUMFLEX;
+        let diphthong = word.len() > 1 && hgk_is_diphthong(word[0].letter, word[1].letter)
+            && !word[1].diacritics.contains(Diacritics::DIAERESIS) && !word[0].diacritics.intersects(accents);
+        let b = if diphthong { 1 } else { 0 };
+        let rough = word[0].diacritics.contains(Diacritics::ROUGH);
+        word[0].diacritics.remove(Diacritics::ROUGH);
+        word[b].diacritics.insert(if rough { Diacritics::ROUGH } else { Diacritics::SMOOTH });
+    }
+}
+
+/// Parse scientific or SBL romanization back into greek letters.  Smooth breathing is added
+/// to initial vowels without h.  SBL has no accents; ALA-LC is parsed as SBL, so its iota
+/// subscripts come back as a full iota.
+pub fn hgk_deromanize_letters(text:&str, scheme:HgkRomanization) -> Vec<HGKLetter> {
+    let mut res = Vec::new();
+    let mut word = String::new();
+    for c in text.nfd() {
+        if c.is_ascii_alphabetic() || (!word.is_empty() && ('\u{0300}'..='\u{036F}').contains(&c)) {
+            word.push(c);
+            continue;
+        }
+        parse_word(&word, scheme, &mut res);
+        word.clear();
+        res.push(HGKLetter::new(c, Diacritics::NONE));
+    }
+    parse_word(&word, scheme, &mut res);
+    res
+}
+
+pub fn hgk_deromanize<'a>(text:&str, scheme:HgkRomanization, unicode_mode:impl Into<HgkOutputMode<'a>>) -> String {
+    let unicode_mode = unicode_mode.into();
+    hgk_deromanize_letters(text, scheme).iter().map(|l| l.to_string(unicode_mode)).collect::<String>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deromanize() {
+        let d = |s| hgk_deromanize(s, HgkRomanization::Scientific, HgkUnicodeMode::Precomposed);
+        assert_eq!(d("ánthrōpos"), "ἄνθρωπος");
+        assert_eq!(d("hoi autoì"), "οἱ αὐτοὶ");
+        assert_eq!(d("Hómēros, rhḗtōr"), "Ὅμηρος, ῥήτωρ");
+        assert_eq!(d("ā́"), "\u{1FB1}\u{0313}\u{0301}");
+        assert_eq!(hgk_deromanize("ā́", HgkRomanization::Scientific, HgkUnicodeMode::PrecomposedPUA), "\u{EB07}");
+        assert_eq!(hgk_deromanize("angelos tę̄", HgkRomanization::Sbl, HgkUnicodeMode::Precomposed), "ἀγγελος τῃ");
+    }
+
+    #[test]
+    fn deromanize_round_trip() {
+        let words = "ἄνθρωπος λόγου καὶ οἱ αὐτοὶ ῥήτορες ἀΐδιος Ὅμηρος υἱός ᾠδή ῥᾳδίως τῆς ἄγγελος σφίγξ \
+                     Ἅιδης ψυχή χρῆμα φιλοσοφία Ζεύς ἐξ \u{EB07}μα ᾱ̔ ἰσχῡ́ς ἠώς ὦ εὐθύς ΘΕΟΣ";
+        let roman = hgk_romanize(words, HgkRomanization::Scientific);
+        assert_eq!(hgk_deromanize(&roman, HgkRomanization::Scientific, HgkUnicodeMode::Precomposed), hgk_convert(words, HgkUnicodeMode::Precomposed), "{}", roman);
+    }
+}
@@ -0,0 +1,360 @@
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign};
+
+use crate::tables::*;
+
+const ALL_BITS:u32 = HGK_ROUGH | HGK_SMOOTH | HGK_ACUTE | HGK_GRAVE | HGK_CIRCUMFLEX | HGK_MACRON | HGK_BREVE | HGK_IOTA_SUBSCRIPT | HGK_DIAERESIS | HGK_UNDERDOT;
+
+//canonical order, the same as the bit order of the HGK_ constants
+const DIACRITIC_NAMES: &[(u32, &str)] = &[
+    (HGK_ROUGH, "ROUGH"),
+    (HGK_SMOOTH, "SMOOTH"),
+    (HGK_ACUTE, "ACUTE"),
+    (HGK_GRAVE, "GRAVE"),
+    (HGK_CIRCUMFLEX, "CIRCUMFLEX"),
+    (HGK_MACRON, "MACRON"),
+    (HGK_BREVE, "BREVE"),
+    (HGK_IOTA_SUBSCRIPT, "IOTA_SUBSCRIPT"),
+    (HGK_DIAERESIS, "DIAERESIS"),
+    (HGK_UNDERDOT, "UNDERDOT")
+];
+
+/// A set of diacritics.  Only the bits of the HGK_ diacritic constants can be set.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Diacritics(u32);
+
+impl Diacritics {
+    pub const NONE: Diacritics = Diacritics(HGK_NO_DIACRITICS);
+    pub const ROUGH: Diacritics = Diacritics(HGK_ROUGH);
+    pub const SMOOTH: Diacritics = Diacritics(HGK_SMOOTH);
+    pub const ACUTE: Diacritics = Diacritics(HGK_ACUTE);
+    pub const GRAVE: Diacritics = Diacritics(HGK_GRAVE);
+    pub const CIRCUMFLEX: Diacritics = Diacritics(HGK_CIRCUMFLEX);
+    pub const MACRON: Diacritics = Diacritics(HGK_MACRON);
+    pub const BREVE: Diacritics = Diacritics(HGK_BREVE);
+    pub const IOTA_SUBSCRIPT: Diacritics = Diacritics(HGK_IOTA_SUBSCRIPT);
+    pub const DIAERESIS: Diacritics = Diacritics(HGK_DIAERESIS);
+    pub const UNDERDOT: Diacritics = Diacritics(HGK_UNDERDOT);
+    pub const ALL: Diacritics = Diacritics(ALL_BITS);
+
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns None if bits contains anything other than diacritic bits.
+    pub const fn from_bits(bits:u32) -> Option<Diacritics> {
+        if bits & !ALL_BITS == 0 {
+            Some(Diacritics(bits))
+        }
+        else {
+            None
+        }
+    }
+
+    /// Drops any bits which are not diacritic bits.
+    pub const fn from_bits_truncate(bits:u32) -> Diacritics {
+        Diacritics(bits & ALL_BITS)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == HGK_NO_DIACRITICS
+    }
+
+    /// True if exactly one diacritic is set.
+    pub const fn is_single(&self) -> bool {
+        self.0.count_ones() == 1
+    }
+
+    pub const fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn contains(&self, other:Diacritics) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn intersects(&self, other:Diacritics) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub const fn union(self, other:Diacritics) -> Diacritics {
+        Diacritics(self.0 | other.0)
+    }
+
+    pub const fn intersection(self, other:Diacritics) -> Diacritics {
+        Diacritics(self.0 & other.0)
+    }
+
+    pub const fn difference(self, other:Diacritics) -> Diacritics {
+        Diacritics(self.0 & !other.0)
+    }
+
+    pub const fn symmetric_difference(self, other:Diacritics) -> Diacritics {
+        Diacritics(self.0 ^ other.0)
+    }
+
+    pub const fn complement(self) -> Diacritics {
+        Diacritics(!self.0 & ALL_BITS)
+    }
+
+    pub fn insert(&mut self, other:Diacritics) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other:Diacritics) {
+        self.0 &= !other.0;
+    }
+
+    pub fn toggle(&mut self, other:Diacritics) {
+        self.0 ^= other.0;
+    }
+
+    /// Iterate over the single diacritics in the set, in canonical order.
+    pub fn iter(&self) -> DiacriticsIter {
+        DiacriticsIter { diacritics: *self, index: 0 }
+    }
+
+    /// The name of a single diacritic, e.g. "ACUTE".
+    pub fn name(&self) -> Option<&'static str> {
+        DIACRITIC_NAMES.iter().find(|n| n.0 == self.0).map(|n| n.1)
+    }
+
+    /// The diacritic with the given name, ignoring case, e.g. "acute".
+    pub fn from_name(name:&str) -> Option<Diacritics> {
+        DIACRITIC_NAMES.iter().find(|n| n.1.eq_ignore_ascii_case(name)).map(|n| Diacritics(n.0))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DiacriticsIter {
+    diacritics: Diacritics,
+    index: usize
+}
+
+impl Iterator for DiacriticsIter {
+    type Item = Diacritics;
+
+    fn next(&mut self) -> Option<Diacritics> {
+        while self.index < DIACRITIC_NAMES.len() {
+            let bit = DIACRITIC_NAMES[self.index].0;
+            self.index += 1;
+            if self.diacritics.0 & bit != 0 {
+                return Some(Diacritics(bit));
+            }
+        }
+        None
+    }
+}
+
+impl IntoIterator for Diacritics {
+    type Item = Diacritics;
+    type IntoIter = DiacriticsIter;
+
+    fn into_iter(self) -> DiacriticsIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Diacritics> for Diacritics {
+    fn from_iter<I: IntoIterator<Item = Diacritics>>(iter:I) -> Diacritics {
+        iter.into_iter().fold(Diacritics::NONE, |acc, d| acc | d)
+    }
+}
+
+impl fmt::Debug for Diacritics {
+    fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Diacritics(")?;
+        if self.is_empty() {
+            write!(f, "NONE")?;
+        }
+        for (i, d) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " | ")?;
+            }
+            write!(f, "{}", d.name().unwrap_or("?"))?;
+        }
+        write!(f, ")")
+    }
+}
+
+//for backward compatibility with the HGK_ u32 constants
+impl From<u32> for Diacritics {
+    fn from(bits:u32) -> Diacritics {
+        Diacritics::from_bits_truncate(bits)
+    }
+}
+
+impl From<Diacritics> for u32 {
+    fn from(d:Diacritics) -> u32 {
+        d.0
+    }
+}
+
+impl BitOr for Diacritics {
+    type Output = Diacritics;
+    fn bitor(self, rhs:Diacritics) -> Diacritics {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for Diacritics {
+    fn bitor_assign(&mut self, rhs:Diacritics) {
+        self.insert(rhs);
+    }
+}
+
+impl BitAnd for Diacritics {
+    type Output = Diacritics;
+    fn bitand(self, rhs:Diacritics) -> Diacritics {
+        self.intersection(rhs)
+    }
+}
+
+impl BitAndAssign for Diacritics {
+    fn bitand_assign(&mut self, rhs:Diacritics) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl BitXor for Diacritics {
+    type Output = Diacritics;
+    fn bitxor(self, rhs:Diacritics) -> Diacritics {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl BitXorAssign for Diacritics {
+    fn bitxor_assign(&mut self, rhs:Diacritics) {
+        self.toggle(rhs);
+    }
+}
+
+impl Sub for Diacritics {
+    type Output = Diacritics;
+    fn sub(self, rhs:Diacritics) -> Diacritics {
+        self.difference(rhs)
+    }
+}
+
+impl SubAssign for Diacritics {
+    fn sub_assign(&mut self, rhs:Diacritics) {
+        self.remove(rhs);
+    }
+}
+
+impl Not for Diacritics {
+    type Output = Diacritics;
+    fn not(self) -> Diacritics {
+        self.complement()
+    }
+}
+
+pub const HGK_MAX_MARKS:usize = 4;
+
+/// Combining marks after a letter other than the ten diacritics, e.g. U+0311 or U+0333 in
+/// epigraphic editions, in the order they were written.  They are kept so that converting
+/// text does not lose them.  A letter holds at most HGK_MAX_MARKS; any more start a new
+/// letter, as marks without a letter do.
+///
+/// Each mark also keeps which of the letter's diacritics were written after it, so that
+/// output puts it back in the same place, e.g. α\u{0311}\u{0301} is not the same text as
+/// α\u{0301}\u{0311}.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct HgkMarks {
+    marks: [char; HGK_MAX_MARKS],
+    ahead: [Diacritics; HGK_MAX_MARKS], //diacritics written after each mark
+    len: u8
+}
+
+impl HgkMarks {
+    pub const NONE: HgkMarks = HgkMarks { marks: ['\u{0000}'; HGK_MAX_MARKS], ahead: [Diacritics::NONE; HGK_MAX_MARKS], len: 0 };
+
+    /// Add a mark after the letter's diacritics.  Returns false, leaving the marks
+    /// unchanged, if there is no room.
+    pub fn push(&mut self, c:char) -> bool {
+        self.push_before(c, Diacritics::NONE)
+    }
+
+    /// Add a mark which is written before the diacritics in ahead.
+    pub fn push_before(&mut self, c:char, ahead:impl Into<Diacritics>) -> bool {
+        if self.len as usize == HGK_MAX_MARKS {
+            return false;
+        }
+        self.marks[self.len as usize] = c;
+        self.ahead[self.len as usize] = ahead.into();
+        self.len += 1;
+        true
+    }
+
+    //a diacritic d was read after the marks so far
+    pub(crate) fn diacritic_after(&mut self, d:Diacritics) {
+        for a in &mut self.ahead[..self.len as usize] {
+            *a |= d;
+        }
+    }
+
+    pub fn as_slice(&self) -> &[char] {
+        &self.marks[..self.len as usize]
+    }
+
+    pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, char>> {
+        self.as_slice().iter().copied()
+    }
+
+    /// Each mark with the diacritics written after it.
+    pub fn iter_placed(&self) -> impl Iterator<Item = (char, Diacritics)> + '_ {
+        self.iter().zip(self.ahead.iter().copied())
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl fmt::Debug for HgkMarks {
+    fn fmt(&self, f:&mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HgkMarks(")?;
+        for (i, c) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "U+{:04X}", c as u32)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn diacritics_set() {
+        let d = Diacritics::ACUTE | Diacritics::ROUGH | Diacritics::MACRON;
+        assert!(d.contains(Diacritics::ACUTE | Diacritics::ROUGH));
+        assert!(!d.contains(Diacritics::ACUTE | Diacritics::GRAVE));
+        assert!(d.intersects(Diacritics::ACUTE | Diacritics::GRAVE));
+        assert_eq!(d.len(), 3);
+        assert_eq!(d - Diacritics::ACUTE, Diacritics::ROUGH | Diacritics::MACRON);
+        assert_eq!(!Diacritics::NONE, Diacritics::ALL);
+        assert_eq!((!d).len(), 7);
+        assert_eq!(d.iter().collect::<Vec<Diacritics>>(), vec![Diacritics::ROUGH, Diacritics::ACUTE, Diacritics::MACRON]);
+        assert_eq!(d.iter().collect::<Diacritics>(), d);
+        assert_eq!(format!("{:?}", d), "Diacritics(ROUGH | ACUTE | MACRON)");
+        assert_eq!(format!("{:?}", Diacritics::NONE), "Diacritics(NONE)");
+
+        assert_eq!(Diacritics::from(HGK_ACUTE | HGK_GRAVE), Diacritics::ACUTE | Diacritics::GRAVE);
+        assert_eq!(Diacritics::from(0xFFFFFFFF), Diacritics::ALL);
+        assert_eq!(Diacritics::from_bits(0xFFFF), None);
+        assert_eq!(u32::from(Diacritics::UNDERDOT), HGK_UNDERDOT);
+        assert_eq!(Diacritics::from_name("iota_subscript"), Some(Diacritics::IOTA_SUBSCRIPT));
+        assert_eq!(Diacritics::ACUTE.name().and_then(Diacritics::from_name), Some(Diacritics::ACUTE));
+        assert_eq!(Diacritics::from_name("tonos"), None);
+    }
+}
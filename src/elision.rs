@@ -0,0 +1,195 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::*;
+
+fn lower(c:char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_apostrophe(c:char) -> bool {
+    matches!(c, '\u{2019}' | '\u{1FBD}' | '\u{02BC}' | '\'')
+}
+
+//all apostrophes as U+2019
+fn normalize_apostrophes(s:&str) -> String {
+    s.nfc().map(|c| if is_apostrophe(c) { '\u{2019}' } else { c }).collect::<String>()
+}
+
+//lookup key: lower case, grave as acute, so κἀγὼ finds κἀγώ but κἀν and κἄν stay apart
+fn key(word:&str) -> String {
+    normalize_apostrophes(word).gkletters().map(|mut l| {
+        if l.diacritics.contains(Diacritics::GRAVE) {
+            l.diacritics.remove(Diacritics::GRAVE);
+            l.diacritics.insert(Diacritics::ACUTE);
+        }
+        l.letter = lower(l.letter);
+        l.to_string(HgkUnicodeMode::Precomposed)
+    }).collect::<String>()
+}
+
+fn letters_to_string(letters:&[HGKLetter]) -> String {
+    letters.iter().map(|l| l.to_string(HgkUnicodeMode::Precomposed)).collect::<String>()
+}
+
+/// Expands elided and crasis forms to the full forms they may stand for, e.g. ἀλλ’ to ἀλλά
+/// and κἀγώ to καὶ ἐγώ.  new() has a small built-in table; more forms can be added.
+#[derive(Debug, Default)]
+pub struct HgkExpansions {
+    forms: BTreeMap<String, Vec<String>>
+}
+
+impl HgkExpansions {
+    pub fn new() -> HgkExpansions {
+        let mut e = HgkExpansions::default();
+        e.extend(ELISIONS.iter().chain(CRASES.iter()).copied());
+        e
+    }
+
+    /// Add a full form for short, e.g. ("κἀν", "καὶ ἐν").  Forms of one word are kept in the
+    /// order they were added.  Case of short is ignored, and a grave is taken as an acute.
+    pub fn insert(&mut self, short:&str, full:&str) {
+        let full = full.nfc().collect::<String>();
+        let forms = self.forms.entry(key(short)).or_default();
+        if !forms.contains(&full) {
+            forms.push(full);
+        }
+    }
+
+    pub fn extend<'a>(&mut self, pairs:impl IntoIterator<Item = (&'a str, &'a str)>) {
+        for (short, full) in pairs {
+            self.insert(short, full);
+        }
+    }
+
+    /// The candidate full forms of word, each one or more words separated by spaces.
+    ///
+    /// Elided words not in the table are expanded by rule: each short vowel is added, with
+    /// φ θ χ also tried as π τ κ.  These may be wrongly accented, so they are mostly useful
+    /// for accent insensitive matching.  Crasis forms are only expanded from the table.
+    pub fn expand(&self, word:&str) -> Vec<String> {
+        if let Some(forms) = self.forms.get(&key(word)) {
+            return forms.clone();
+        }
+
+        let word = normalize_apostrophes(word);
+        let letters = word.gkletters().filter(|l| hgk_is_greek_letter(l.letter)).collect::<Vec<HGKLetter>>();
+        let mut res = Vec::new();
+        if word.ends_with('\u{2019}') && !letters.is_empty() {
+            let mut stems = vec![letters_to_string(&letters)];
+            let mut plain = letters.clone();
+            let last = plain.last_mut().unwrap();
+            if let Some(p) = match last.letter { 'φ' => Some('π'), 'θ' => Some('τ'), 'χ' => Some('κ'), _ => None } {
+                last.letter = p;
+                stems.push(letters_to_string(&plain));
+            }
+            for s in stems {
+                for v in ['α', 'ε', 'ι', 'ο'] {
+                    res.push(format!("{}{}", s, v));
+                }
+            }
+        }
+        res
+    }
+
+    //the table keyed as hgk_compare() with compare_type, so entries which match ignoring
+    //compare_type are found together
+    fn index(&self, compare_type:Diacritics) -> BTreeMap<GreekKey, Vec<&str>> {
+        let mut index = BTreeMap::<GreekKey, Vec<&str>>::new();
+        for (short, forms) in &self.forms {
+            index.entry(GreekKey::new(short, compare_type)).or_default().extend(forms.iter().map(String::as_str));
+        }
+        index
+    }
+
+    //the word and every word of its expansions, taking the forms of every table entry
+    //which matches word ignoring compare_type
+    fn forms_of(&self, index:&BTreeMap<GreekKey, Vec<&str>>, word:&str, compare_type:Diacritics) -> Vec<String> {
+        let mut forms = vec![normalize_apostrophes(word)];
+        let fulls = match index.get(&GreekKey::new(&key(word), compare_type)) {
+            Some(f) => f.iter().map(|f| String::from(*f)).collect::<Vec<String>>(),
+            None => self.expand(word)
+        };
+        for full in fulls {
+            forms.extend(full.split_whitespace().map(String::from));
+        }
+        forms
+    }
+}
+
+/// The byte ranges of the words in text which match query, a single word, ignoring the
+/// diacritics in compare_type as in hgk_compare().  With expansions, a word also matches
+/// if any word of its expanded forms does, and the same for the query, so ἐγώ finds κἀγώ
+/// and ἀλλά finds ἀλλ’.
+pub fn hgk_search(text:&str, query:&str, compare_type:impl Into<Diacritics>, expansions:Option<&HgkExpansions>) -> Vec<Range<usize>> {
+    let compare_type = compare_type.into();
+    let index = expansions.map(|e| e.index(compare_type));
+    let query_forms = match (expansions, &index) {
+        (Some(e), Some(index)) => e.forms_of(index, query, compare_type),
+        _ => vec![normalize_apostrophes(query)]
+    };
+
+    hgk_tokenize(text).into_iter().filter(|t| t.kind == HgkTokenKind::Word).filter(|t| {
+        let forms = match (expansions, &index) {
+            (Some(e), Some(index)) => e.forms_of(index, t.text, compare_type),
+            _ => vec![normalize_apostrophes(t.text)]
+        };
+        forms.iter().any(|f| query_forms.iter().any(|q| hgk_compare(f, q, compare_type) == 0))
+    }).map(|t| t.range).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand() {
+        let e = HgkExpansions::new();
+        assert_eq!(e.expand("ἀλλ᾽"), vec!["ἀλλά"]);
+        assert_eq!(e.expand("Κἀγὼ"), vec!["καὶ ἐγώ"]);
+        assert_eq!(e.expand("κἄν"), vec!["καὶ ἄν", "καὶ ἐάν"]);
+        assert_eq!(e.expand("κἀν"), vec!["καὶ ἐν"]);
+        assert_eq!(e.expand("θοὔδωρ"), vec!["τὸ ὕδωρ"]);
+        assert_eq!(e.expand("λόγος"), Vec::<String>::new());
+
+        //aspirated forms in the table
+        assert_eq!(e.expand("ἀφ’"), vec!["ἀπό"]);
+        assert_eq!(e.expand("ὑφ’"), vec!["ὑπό"]);
+        assert_eq!(e.expand("ἐφ’"), vec!["ἐπί"]);
+        assert_eq!(e.expand("Ἐφ’"), vec!["ἐπί"]);
+
+        //by rule, not in the table
+        assert_eq!(e.expand("νύχθ’"), vec!["νύχθα", "νύχθε", "νύχθι", "νύχθο", "νύχτα", "νύχτε", "νύχτι", "νύχτο"]);
+        assert_eq!(e.expand("κἀπί"), Vec::<String>::new());
+
+        let mut e = HgkExpansions::default();
+        assert_eq!(e.expand("κἀν"), Vec::<String>::new());
+        e.extend([("κἀν", "καὶ ἐν")]);
+        assert_eq!(e.expand("κἀν"), vec!["καὶ ἐν"]);
+    }
+
+    #[test]
+    fn search() {
+        let text = "ἀλλ’ οὐ κἀγὼ λέγω, ἀλλὰ σύ· ἐγὼ δ’ οὔ.";
+        let e = HgkExpansions::new();
+        let found = |q, ignore, e| hgk_search(text, q, ignore, e).into_iter().map(|r| &text[r]).collect::<Vec<_>>();
+
+        assert_eq!(found("ἀλλά", HGK_ACUTE | HGK_GRAVE, None), vec!["ἀλλὰ"]);
+        assert_eq!(found("ἀλλά", HGK_ACUTE | HGK_GRAVE, Some(&e)), vec!["ἀλλ’", "ἀλλὰ"]);
+        assert_eq!(found("ἐγώ", HGK_ACUTE | HGK_GRAVE, Some(&e)), vec!["κἀγὼ", "ἐγὼ"]);
+        assert_eq!(found("ἐγώ", 0, Some(&e)), vec!["κἀγὼ"]);
+        assert_eq!(found("δέ", 0xFFFFFFFF, Some(&e)), vec!["δ’"]);
+        assert_eq!(found("ἀλλ᾽", 0, Some(&e)), vec!["ἀλλ’"]);
+
+        //accents are only ignored when searching, so ἐάν finds κἄν but not κἀν
+        let text = "κἀν κἄν";
+        let found = |q, ignore| hgk_search(text, q, ignore, Some(&e)).into_iter().map(|r| &text[r]).collect::<Vec<_>>();
+        assert_eq!(found("ἐάν", 0), vec!["κἄν"]);
+        assert_eq!(found("ἐν", 0), vec!["κἀν"]);
+        assert_eq!(found("ἐν", HGK_ACUTE | HGK_GRAVE), vec!["κἀν", "κἄν"]);
+    }
+}
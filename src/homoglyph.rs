@@ -0,0 +1,112 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::*;
+
+/// A latin or cyrillic letter in a greek word which looks like a greek letter.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HgkHomoglyph {
+    pub range: Range<usize>, //the letter and its diacritics
+    pub word: Range<usize>,
+    pub found: char,
+    pub replacement: HGKLetter
+}
+
+//the greek letter l looks like, keeping its diacritics.  Latin letters with precomposed
+//accents, e.g. ó, are decomposed first.
+fn greek_lookalike(l:&HGKLetter) -> Option<HGKLetter> {
+    let decomposed = l.letter.to_string().nfd().collect::<String>();
+    let mut base = decomposed.gkletters().next()?;
+    let greek = HOMOGLYPHS.iter().find(|h| h.0 == base.letter)?.1;
+    base.letter = greek;
+    base.diacritics |= l.diacritics;
+    for (m, ahead) in l.marks.iter_placed() {
+        base.marks.push_before(m, ahead);
+    }
+    Some(base)
+}
+
+fn check_word(word:&[(Range<usize>, HGKLetter)], res:&mut Vec<HgkHomoglyph>) {
+    //a greek word has at least one greek letter, and every other letter has a greek lookalike
+    if !word.iter().any(|(_, l)| hgk_is_greek_letter(l.letter)) {
+        return;
+    }
+    let mut found = Vec::new();
+    for (range, l) in word {
+        if hgk_is_greek_letter(l.letter) {
+            continue;
+        }
+        match greek_lookalike(l) {
+            Some(replacement) => found.push((range.clone(), l.letter, replacement)),
+            None => return
+        }
+    }
+    let Some(span) = word.first().zip(word.last()).map(|(f, l)| f.0.start..l.0.end) else { return };
+    res.extend(found.into_iter().map(|(range, found, replacement)| HgkHomoglyph { range, word: span.clone(), found, replacement }));
+}
+
+/// Find latin and cyrillic lookalikes, e.g. latin o, v or a, in greek words.  Words which have
+/// a non-greek letter without a greek lookalike are not greek, and are left alone.
+pub fn hgk_find_homoglyphs(text:&str) -> Vec<HgkHomoglyph> {
+    let mut res = Vec::new();
+    let mut word:Vec<(Range<usize>, HGKLetter)> = Vec::new();
+    for (range, letter) in text.gkletter_spans() {
+        if letter.letter.is_alphabetic() {
+            word.push((range, letter));
+        }
+        else {
+            check_word(&word, &mut res);
+            word.clear();
+        }
+    }
+    check_word(&word, &mut res);
+    res
+}
+
+/// Replace the homoglyphs found by hgk_find_homoglyphs() with their greek letters, written in
+/// unicode_mode.  The rest of the text is unchanged.  Returns the repaired text and every
+/// substitution, with ranges in the original text.
+pub fn hgk_repair_homoglyphs<'a>(text:&str, unicode_mode:impl Into<HgkOutputMode<'a>>) -> (String, Vec<HgkHomoglyph>) {
+    let unicode_mode = unicode_mode.into();
+    let found = hgk_find_homoglyphs(text);
+    let mut res = String::with_capacity(text.len());
+    let mut last = 0;
+    for h in &found {
+        res.push_str(&text[last..h.range.start]);
+        res.push_str(&h.replacement.to_string(unicode_mode));
+        last = h.range.end;
+    }
+    res.push_str(&text[last..]);
+    (res, found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn homoglyphs() {
+        //latin o, latin ā, latin v and ó, cyrillic а
+        let text = "λόγoς καὶ \u{0101}νθρωπος v\u{00F3}μος \u{0430}ὐτός topic oἶκος";
+        let found = hgk_find_homoglyphs(text);
+        assert_eq!(found.iter().map(|h| h.found).collect::<String>(), "o\u{0101}v\u{00F3}\u{0430}o");
+        assert_eq!(found[0].range, 6..7);
+        assert_eq!(found[0].word, 0..9);
+        assert_eq!(found[0].replacement, HGKLetter::new('ο', Diacritics::NONE));
+        assert_eq!(found[1].replacement, HGKLetter::new('α', Diacritics::MACRON));
+        assert_eq!(found[3].replacement, HGKLetter::new('ο', Diacritics::ACUTE));
+
+        let (repaired, subs) = hgk_repair_homoglyphs(text, HgkUnicodeMode::Precomposed);
+        assert_eq!(subs, found);
+        assert_eq!(repaired, "λόγος καὶ \u{1FB1}νθρωπος νόμος αὐτός topic οἶκος");
+        assert!(hgk_find_homoglyphs(&repaired).is_empty());
+        assert_eq!(hgk_compare(&repaired[..10], "λόγος", 0), 0);
+
+        //words with no greek letters, or with latin letters which have no lookalike, are not greek
+        assert!(hgk_find_homoglyphs("ok Kai λόγosx").is_empty());
+        assert_eq!(hgk_repair_homoglyphs("ΛOΓOΣ", HgkUnicodeMode::Precomposed).0, "ΛΟΓΟΣ");
+    }
+}
@@ -0,0 +1,205 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HgkInputMode {
+    Postfix, //diacritic keys are typed after the letter
+    DeadKey  //diacritic keys are typed before the letter
+}
+
+#[derive(PartialEq, Debug)]
+pub enum HgkKeyResult {
+    Insert(HGKLetter),               //append a new letter
+    InsertRejected(HGKLetter, Diacritics, HgkRejectReason), //append a new letter, pending diacritics which were not legal on it are returned, with the reason for the first
+    Replace(HGKLetter),              //replace the previous letter (postfix diacritic)
+    Pending(Diacritics),                //dead key: the diacritics now waiting for the next letter
+    Rejected(Diacritics, Option<HgkRejectReason>), //diacritic is not legal on the previous letter, or there is no previous letter (None)
+    Unmapped                         //key is not in the layout
+}
+
+#[derive(Clone, Debug)]
+pub struct HgkInputEngine {
+    pub layout: HgkKeyboardLayout, //its diacritic keys are the engine's
+    pub mode: HgkInputMode,
+    pending: Vec<Diacritics> //dead key diacritics in the order typed
+}
+
+impl HgkInputEngine {
+    pub fn new(layout:HgkKeyboardLayout, mode:HgkInputMode) -> HgkInputEngine {
+        HgkInputEngine {
+            layout,
+            mode,
+            pending: Vec::new()
+        }
+    }
+
+    /// Assign diacritic d to key in the layout, replacing any previous assignment of key.
+    /// Keys which are not printable ascii are ignored.
+    pub fn set_diacritic_key(&mut self, key:char, d:Diacritics) {
+        self.layout.set_diacritic(key, d);
+    }
+
+    pub fn diacritic_for_key(&self, key:char) -> Option<Diacritics> {
+        self.layout.diacritic(key)
+    }
+
+    /// The dead key diacritics waiting for the next letter.
+    pub fn pending(&self) -> Diacritics {
+        self.pending.iter().copied().collect::<Diacritics>()
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn set_mode(&mut self, mode:HgkInputMode) {
+        self.mode = mode;
+        self.pending.clear();
+    }
+
+    /// Handle one key press.  previous is the letter before the insertion point,
+    /// which postfix diacritics are applied to.
+    pub fn key_press(&mut self, key:char, previous:Option<&HGKLetter>) -> HgkKeyResult {
+        if let Some(d) = self.diacritic_for_key(key) {
+            return match self.mode {
+                HgkInputMode::Postfix => {
+                    match previous {
+                        Some(p) => {
+                            let mut letter = *p;
+                            match letter.try_toggle_diacritic(d, false) {
+                                HgkToggleResult::Rejected(reason) => HgkKeyResult::Rejected(d, Some(reason)),
+                                _ => HgkKeyResult::Replace(letter)
+                            }
+                        },
+                        None => HgkKeyResult::Rejected(d, None)
+                    }
+                },
+                HgkInputMode::DeadKey => {
+                    //typing the same dead key twice cancels it
+                    if self.pending.contains(&d) {
+                        self.pending.retain(|p| *p != d);
+                    }
+                    else {
+                        self.pending.retain(|p| !d.iter().any(|s| hgk_clashing_diacritics(s).intersects(*p)));
+                        self.pending.push(d);
+                    }
+                    HgkKeyResult::Pending(self.pending())
+                }
+            };
+        }
+
+        let ch = match self.layout.get(key) {
+            Some(ch) => ch,
+            None => return HgkKeyResult::Unmapped
+        };
+
+        let mut letter = HGKLetter::from_str(&String::from(ch));
+        let mut rejected = Diacritics::NONE;
+        let mut reason = None;
+        for d in self.pending.drain(..) {
+            //a key may enter more than one diacritic
+            for single in d {
+                match letter.reject_reason(single) {
+                    None => letter.toggle_diacritic(single, true),
+                    Some(r) => {
+                        rejected |= single;
+                        reason.get_or_insert(r);
+                    }
+                }
+            }
+        }
+
+        if let Some(reason) = reason {
+            HgkKeyResult::InsertRejected(letter, rejected, reason)
+        }
+        else {
+            HgkKeyResult::Insert(letter)
+        }
+    }
+
+    /// Type a sequence of keys, ignoring rejected and unmapped keys.  Dead keys pending from
+    /// before are dropped, and dead keys left at the end are not kept.
+    pub fn type_keys<'a>(&mut self, keys:&str, unicode_mode:impl Into<HgkOutputMode<'a>>) -> String {
+        let unicode_mode = unicode_mode.into();
+        self.pending.clear();
+        let mut letters:Vec<HGKLetter> = Vec::new();
+        for key in keys.chars() {
+            match self.key_press(key, letters.last()) {
+                HgkKeyResult::Insert(l) | HgkKeyResult::InsertRejected(l, _, _) => letters.push(l),
+                HgkKeyResult::Replace(l) => {
+                    letters.pop();
+                    letters.push(l);
+                },
+                _ => ()
+            }
+        }
+        self.pending.clear();
+        letters.iter().map(|l| l.to_string(unicode_mode)).collect::<String>()
+    }
+}
+
+impl Default for HgkInputEngine {
+    fn default() -> HgkInputEngine {
+        HgkInputEngine::new(HOPLITE_LAYOUT, HgkInputMode::Postfix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postfix_input() {
+        let mut e = HgkInputEngine::default();
+        assert_eq!(e.type_keys("lo2gos", HgkUnicodeMode::Precomposed), "λὀγοσ");
+        assert_eq!(e.type_keys("a63", HgkUnicodeMode::PrecomposedPUA), "\u{EB00}");
+        assert_eq!(e.type_keys("a33", HgkUnicodeMode::Precomposed), "α");
+
+        assert_eq!(e.key_press('3', None), HgkKeyResult::Rejected(Diacritics::ACUTE, None));
+        let b = HGKLetter::new('β', Diacritics::NONE);
+        assert_eq!(e.key_press('3', Some(&b)), HgkKeyResult::Rejected(Diacritics::ACUTE, Some(HgkRejectReason::AccentNotOnVowel)));
+        let a = HGKLetter::new('α', Diacritics::ACUTE);
+        assert_eq!(e.key_press('5', Some(&a)), HgkKeyResult::Replace(HGKLetter::new('α', Diacritics::CIRCUMFLEX)));
+    }
+
+    #[test]
+    fn dead_key_input() {
+        let mut e = HgkInputEngine::new(HOPLITE_LAYOUT, HgkInputMode::DeadKey);
+        assert_eq!(e.type_keys("l2og3os", HgkUnicodeMode::Precomposed), "λὀγόσ");
+
+        assert_eq!(e.key_press('2', None), HgkKeyResult::Pending(Diacritics::SMOOTH));
+        assert_eq!(e.key_press('3', None), HgkKeyResult::Pending(Diacritics::SMOOTH | Diacritics::ACUTE));
+        //rough replaces smooth
+        assert_eq!(e.key_press('1', None), HgkKeyResult::Pending(Diacritics::ACUTE | Diacritics::ROUGH));
+        assert_eq!(e.key_press('1', None), HgkKeyResult::Pending(Diacritics::ACUTE));
+        assert_eq!(e.key_press('1', None), HgkKeyResult::Pending(Diacritics::ACUTE | Diacritics::ROUGH));
+        assert_eq!(e.key_press('a', None), HgkKeyResult::Insert(HGKLetter::new('α', Diacritics::ROUGH | Diacritics::ACUTE)));
+        assert_eq!(e.pending(), Diacritics::NONE);
+
+        e.key_press('1', None);
+        e.key_press('3', None);
+        assert_eq!(e.key_press('r', None), HgkKeyResult::InsertRejected(HGKLetter::new('ρ', Diacritics::ROUGH), Diacritics::ACUTE, HgkRejectReason::AccentNotOnVowel));
+
+        //dead keys left over do not carry into the next call
+        assert_eq!(e.type_keys("l2", HgkUnicodeMode::Precomposed), "λ");
+        assert_eq!(e.pending(), Diacritics::NONE);
+        assert_eq!(e.type_keys("a", HgkUnicodeMode::Precomposed), "α");
+        e.key_press('3', None);
+        assert_eq!(e.type_keys("a", HgkUnicodeMode::Precomposed), "α");
+    }
+
+    #[test]
+    fn preset_diacritic_keys() {
+        let mut e = HgkInputEngine::new(HgkKeyboardLayout::preset(HgkLayoutPreset::BetaCode), HgkInputMode::Postfix);
+        assert_eq!(e.type_keys("a)/nqrwpos", HgkUnicodeMode::Precomposed), "ἄνθρωποσ");
+        assert_eq!(e.type_keys("th=|", HgkUnicodeMode::Precomposed), "τῇ");
+        assert_eq!(e.type_keys("a1", HgkUnicodeMode::Precomposed), "α");
+        assert_eq!(e.diacritic_for_key('1'), None);
+
+        let mut e = HgkInputEngine::new(HgkKeyboardLayout::preset(HgkLayoutPreset::ModernGreek), HgkInputMode::DeadKey);
+        assert_eq!(e.type_keys("l;ogow", HgkUnicodeMode::Precomposed), "λόγος");
+    }
+}
@@ -0,0 +1,329 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HgkIpaPeriod {
+    Attic,  //5th c. BCE: vowel length, pitch accent, aspirated stops
+    Koine,  //about 4th c. CE: stress accent, fricatives, no length or h
+    Modern
+}
+
+const ACCENTS:Diacritics = Diacritics::ACUTE.union(Diacritics::GRAVE).union(Diacritics::CIRCUMFLEX);
+
+//a vowel or diphthong, or a consonant
+#[derive(Clone, Debug)]
+enum Segment {
+    Vowel { first: char, second: Option<char>, diacritics: Diacritics },
+    Consonant { letter: char, diacritics: Diacritics }
+}
+
+fn lower(c:char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn segments(word:&[HGKLetter]) -> Vec<Segment> {
+    let mut res = Vec::new();
+    let mut i = 0;
+    while i < word.len() {
+        let c = lower(word[i].letter);
+        if c.is_greek_vowel() {
+            let second = word.get(i + 1).filter(|n| hgk_is_diphthong(c, lower(n.letter)) && !n.diacritics.contains(Diacritics::DIAERESIS));
+            match second {
+                Some(n) => {
+                    res.push(Segment::Vowel { first: c, second: Some(lower(n.letter)), diacritics: word[i].diacritics | n.diacritics });
+                    i += 2;
+                },
+                None => {
+                    res.push(Segment::Vowel { first: c, second: None, diacritics: word[i].diacritics });
+                    i += 1;
+                }
+            }
+        }
+        else {
+            res.push(Segment::Consonant { letter: c, diacritics: word[i].diacritics });
+            i += 1;
+        }
+    }
+    res
+}
+
+fn attic_vowel(first:char, second:Option<char>, d:Diacritics) -> String {
+    let long = matches!(first, 'η' | 'ω') || d.intersects(Diacritics::MACRON | Diacritics::CIRCUMFLEX | Diacritics::IOTA_SUBSCRIPT);
+    let (quality, length) = match (first, second) {
+        ('α', Some('ι')) => ("ai̯", ""),
+        ('ε', Some('ι')) => ("e", "ː"),
+        ('ο', Some('ι')) => ("oi̯", ""),
+        ('υ', Some('ι')) => ("yi̯", ""),
+        ('ο', Some('υ')) => ("o", "ː"),
+        ('α', Some('υ')) => ("au̯", ""),
+        ('ε', Some('υ')) => ("eu̯", ""),
+        ('η', Some('υ')) => ("ɛːu̯", ""),
+        ('ω', Some('υ')) => ("ɔːu̯", ""),
+        (v, _) => (match v { 'α' => "a", 'ε' => "e", 'η' => "ɛ", 'ι' => "i", 'ο' => "o", 'υ' => "y", _ => "ɔ" }, if long { "ː" } else { "" })
+    };
+
+    //pitch: acute is high, circumflex falling, marked on the first vowel
+    let mut chars = quality.chars();
+    let mut s = String::new();
+    s.extend(chars.next());
+    if d.contains(Diacritics::ACUTE) {
+        s.push('\u{0301}');
+    }
+    else if d.contains(Diacritics::CIRCUMFLEX) {
+        s.push('\u{0302}');
+    }
+    s.extend(chars);
+    s.push_str(length);
+    if d.contains(Diacritics::IOTA_SUBSCRIPT) {
+        s.push_str("i̯");
+    }
+    s
+}
+
+//the diphthongs in αυ, ευ, ηυ become a vowel and a fricative
+fn later_vowel(first:char, second:Option<char>, period:HgkIpaPeriod) -> (&'static str, bool) {
+    let modern = period == HgkIpaPeriod::Modern;
+    match (first, second) {
+        ('α', Some('ι')) => ("e", false),
+        ('ε', Some('ι')) => ("i", false),
+        ('ο', Some('ι')) | ('υ', Some('ι')) => (if modern { "i" } else { "y" }, false),
+        ('ο', Some('υ')) => ("u", false),
+        ('α', Some('υ')) => ("a", true),
+        ('ε', Some('υ')) => ("e", true),
+        ('η', Some('υ')) => ("i", true),
+        ('ω', Some('υ')) => ("o", true),
+        ('α', _) => ("a", false),
+        ('ε', _) => ("e", false),
+        ('η', _) | ('ι', _) => ("i", false),
+        ('υ', _) => (if modern { "i" } else { "y" }, false),
+        _ => ("o", false)
+    }
+}
+
+fn is_voiced_ipa(s:&str) -> bool {
+    s.starts_with(['b', 'd', 'ɡ', 'β', 'ð', 'ɣ', 'ʝ', 'v', 'z', 'm', 'n', 'l', 'r', 'ŋ', 'ɱ', 'ɲ', 'ʎ'])
+}
+
+fn is_front_ipa(s:&str) -> bool {
+    s.starts_with(['e', 'i', 'y', 'ɛ'])
+}
+
+fn is_vowel_ipa(s:&str) -> bool {
+    s.starts_with(['a', 'e', 'i', 'o', 'u', 'y', 'ɛ', 'ɔ'])
+}
+
+fn attic_consonant(c:char, d:Diacritics, next:Option<char>) -> &'static str {
+    match c {
+        'β' => "b",
+        'γ' => if matches!(next, Some('γ' | 'κ' | 'ξ' | 'χ')) { "ŋ" } else { "ɡ" },
+        'δ' => "d",
+        'ζ' => "zd",
+        'θ' => "tʰ",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "ks",
+        'π' => "p",
+        'ρ' => if d.contains(Diacritics::ROUGH) { "r̥" } else { "r" },
+        'σ' | 'ς' => if matches!(next, Some('β' | 'γ' | 'δ' | 'μ')) { "z" } else { "s" },
+        'τ' => "t",
+        'φ' => "pʰ",
+        'χ' => "kʰ",
+        'ψ' => "ps",
+        'ϝ' => "w",
+        _ => ""
+    }
+}
+
+//koine and modern consonants; next_sound is the ipa which follows, next_letter the following consonant letter
+fn later_consonant(c:char, prev:Option<char>, next_letter:Option<char>, next_sound:&str, initial:bool, period:HgkIpaPeriod) -> (&'static str, bool) {
+    let modern = period == HgkIpaPeriod::Modern;
+    let front = is_front_ipa(next_sound);
+    //returns the sound and whether the next letter was used up
+    match c {
+        'β' => (if modern { "v" } else { "β" }, false),
+        'γ' => match next_letter {
+            Some('γ') => ("ŋ", false),
+            Some('κ') if modern && initial => ("", false),
+            Some('κ' | 'ξ' | 'χ') => ("ŋ", false),
+            _ if prev == Some('γ') => (if modern && front { "ɟ" } else { "ɡ" }, false),
+            _ => (if front { "ʝ" } else { "ɣ" }, false)
+        },
+        'δ' => ("ð", false),
+        'ζ' => ("z", false),
+        'θ' => ("θ", false),
+        'κ' => (if modern && prev == Some('γ') { if front { "ɟ" } else { "ɡ" } } else if modern && front { "c" } else { "k" }, false),
+        'λ' => ("l", false),
+        'μ' => if modern && next_letter == Some('π') { (if initial { "b" } else { "mb" }, true) } else { ("m", false) },
+        'ν' => if modern && next_letter == Some('τ') { (if initial { "d" } else { "nd" }, true) } else { ("n", false) },
+        'ξ' => ("ks", false),
+        'π' => ("p", false),
+        'ρ' => ("r", false),
+        'σ' | 'ς' => (if is_voiced_ipa(next_sound) && !is_vowel_ipa(next_sound) { "z" } else { "s" }, false),
+        'τ' => match next_letter {
+            Some('ζ') if modern => ("dz", true),
+            Some('σ' | 'ς') if modern => ("ts", true),
+            _ => ("t", false)
+        },
+        'φ' => (if modern { "f" } else { "ɸ" }, false),
+        'χ' => (if front { "ç" } else { "x" }, false),
+        'ψ' => ("ps", false),
+        _ => ("", false)
+    }
+}
+
+//muta cum liquida and s + stop begin a syllable together
+fn onset_pair(a:char, b:char) -> bool {
+    (matches!(a, 'π' | 'β' | 'φ' | 'τ' | 'δ' | 'θ' | 'κ' | 'γ' | 'χ') && matches!(b, 'λ' | 'ρ' | 'μ' | 'ν'))
+        || (matches!(a, 'σ') && matches!(b, 'π' | 'τ' | 'κ' | 'φ' | 'θ' | 'χ'))
+}
+
+fn transcribe_word(word:&[HGKLetter], period:HgkIpaPeriod, res:&mut String) {
+    let segs = segments(word);
+    let mut sounds:Vec<String> = vec![String::new(); segs.len()];
+
+    //vowels first, since consonants depend on what follows
+    for (i, s) in segs.iter().enumerate() {
+        if let Segment::Vowel { first, second, diacritics } = s {
+            sounds[i] = match period {
+                HgkIpaPeriod::Attic => {
+                    let h = if diacritics.contains(Diacritics::ROUGH) { "h" } else { "" };
+                    format!("{}{}", h, attic_vowel(*first, *second, *diacritics))
+                },
+                _ => {
+                    let (v, fricative) = later_vowel(*first, *second, period);
+                    let mut v = String::from(v);
+                    if fricative {
+                        //voiced before vowels and voiced consonants
+                        let voiced = match segs.get(i + 1) {
+                            None => false,
+                            Some(Segment::Vowel { .. }) => true,
+                            Some(Segment::Consonant { letter, .. }) => matches!(letter, 'β' | 'γ' | 'δ' | 'ζ' | 'λ' | 'μ' | 'ν' | 'ρ')
+                        };
+                        v.push_str(match (period, voiced) {
+                            (HgkIpaPeriod::Modern, true) => "v",
+                            (HgkIpaPeriod::Modern, false) => "f",
+                            (_, true) => "β",
+                            (_, false) => "ɸ"
+                        });
+                    }
+                    v
+                }
+            };
+        }
+    }
+
+    for i in (0..segs.len()).rev() {
+        if let Segment::Consonant { letter, diacritics } = &segs[i] {
+            let next_letter = match segs.get(i + 1) {
+                Some(Segment::Consonant { letter, .. }) => Some(*letter),
+                _ => None
+            };
+            if period == HgkIpaPeriod::Attic {
+                sounds[i] = String::from(attic_consonant(*letter, *diacritics, next_letter));
+                continue;
+            }
+            let prev = match i.checked_sub(1).map(|p| &segs[p]) {
+                Some(Segment::Consonant { letter, .. }) => Some(*letter),
+                _ => None
+            };
+            let next_sound = sounds[i + 1..].iter().find(|s| !s.is_empty()).cloned().unwrap_or_default();
+            let (sound, used_next) = later_consonant(*letter, prev, next_letter, &next_sound, i == 0, period);
+            sounds[i] = String::from(sound);
+            if used_next {
+                sounds[i + 1].clear();
+            }
+        }
+    }
+
+    //koine and modern mark the stressed syllable
+    let stressed = segs.iter().position(|s| matches!(s, Segment::Vowel { diacritics, .. } if diacritics.intersects(ACCENTS)));
+    let mut mark_at = None;
+    if period != HgkIpaPeriod::Attic && segs.iter().filter(|s| matches!(s, Segment::Vowel { .. })).count() > 1 {
+        if let Some(v) = stressed {
+            let consonants = segs[..v].iter().rev().take_while(|s| matches!(s, Segment::Consonant { .. })).count();
+            let start = v - consonants;
+            let onset = if start == 0 || consonants <= 1 {
+                consonants
+            }
+            else {
+                match (&segs[v - 2], &segs[v - 1]) {
+                    (Segment::Consonant { letter: a, .. }, Segment::Consonant { letter: b, .. }) if onset_pair(*a, *b) => 2,
+                    _ => 1
+                }
+            };
+            mark_at = Some(v - onset);
+        }
+    }
+
+    for (i, s) in sounds.iter().enumerate() {
+        if mark_at == Some(i) {
+            res.push('ˈ');
+        }
+        res.push_str(s);
+    }
+}
+
+/// A broad IPA transcription of greek text in the pronunciation of period.  Anything which
+/// is not a greek letter is copied unchanged.
+pub fn hgk_to_ipa(text:&str, period:HgkIpaPeriod) -> String {
+    let (ranges, letters):(Vec<Range<usize>>, Vec<HGKLetter>) = text.gkletter_spans().unzip();
+    let mut res = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < letters.len() {
+        if hgk_is_greek_letter(letters[i].letter) && letters[i].letter != HGK_KERAIA && letters[i].letter != HGK_LOWER_KERAIA {
+            let end = letters[i..].iter().position(|l| !hgk_is_greek_letter(l.letter)).map_or(letters.len(), |p| i + p);
+            //only the transcription is composed, other text is left as it was
+            let mut word = String::new();
+            transcribe_word(&letters[i..end], period, &mut word);
+            res.extend(word.nfc());
+            i = end;
+        }
+        else {
+            res.push_str(&text[ranges[i].clone()]);
+            i += 1;
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ipa_attic() {
+        let ipa = |s| hgk_to_ipa(s, HgkIpaPeriod::Attic);
+        assert_eq!(ipa("ἄνθρωπος"), "ántʰrɔːpos");
+        assert_eq!(ipa("ἡμέρα"), "hɛːméra");
+        assert_eq!(ipa("οἶκος εὐθύς"), "ôi̯kos eu̯tʰýs");
+        assert_eq!(ipa("τῇ ῥήτορι"), "tɛ̂ːi̯ r̥ɛ́ːtori");
+        assert_eq!(ipa("ἄγγελος, ζῷον"), "áŋɡelos, zdɔ̂ːi̯on");
+        assert_eq!(ipa("μοῦσα"), "môːsa");
+        assert_eq!(ipa("e\u{0301} λόγος"), "e\u{0301} lóɡos");
+    }
+
+    #[test]
+    fn ipa_koine_and_modern() {
+        assert_eq!(hgk_to_ipa("ἄνθρωπος", HgkIpaPeriod::Koine), "ˈanθropos");
+        assert_eq!(hgk_to_ipa("εὐθύς", HgkIpaPeriod::Koine), "eɸˈθys");
+        assert_eq!(hgk_to_ipa("ἡμέρα", HgkIpaPeriod::Koine), "iˈmera");
+
+        let ipa = |s| hgk_to_ipa(s, HgkIpaPeriod::Modern);
+        assert_eq!(ipa("ἄνθρωπος"), "ˈanθropos");
+        assert_eq!(ipa("εὐθύς"), "efˈθis");
+        assert_eq!(ipa("παιδίον"), "peˈðion");
+        assert_eq!(ipa("γέφυρα"), "ˈʝefira");
+        assert_eq!(ipa("πέντε μπάλα"), "ˈpende ˈbala");
+        assert_eq!(ipa("αὐγή"), "avˈʝi");
+        assert_eq!(ipa("κόσμος"), "ˈkozmos");
+        assert_eq!(ipa("ἄγγελος"), "ˈaŋɟelos");
+    }
+}
@@ -0,0 +1,113 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+use crate::*;
+
+//one letter as hgk_compare() sees it.  Non-greek sorts before greek.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+enum KeyElement {
+    NonGreek(char),
+    Greek(u32, Diacritics) //sort weight, diacritics not ignored
+}
+
+fn key_elements(text:&str, compare_type:Diacritics) -> Vec<KeyElement> {
+    text.gkletters().map(|l| match hgk_sort_weight(l.letter) {
+        Some(w) => KeyElement::Greek(w, l.diacritics - compare_type),
+        None => KeyElement::NonGreek(l.letter)
+    }).collect()
+}
+
+/// A string which compares, hashes and sorts as hgk_compare() with compare_type, so that e.g.
+/// λόγος and ΛΟΓΟΣ are one key in a HashMap when accents are ignored.  Case and σ/ς are
+/// always ignored.  Keys made with different compare_types should not be mixed.
+#[derive(Clone, Debug)]
+pub struct GreekKey {
+    text: String,
+    compare_type: Diacritics,
+    elements: Vec<KeyElement>
+}
+
+impl GreekKey {
+    pub fn new(text:&str, compare_type:impl Into<Diacritics>) -> GreekKey {
+        let compare_type = compare_type.into();
+        GreekKey { text: String::from(text), compare_type, elements: key_elements(text, compare_type) }
+    }
+
+    /// The text the key was made from.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn compare_type(&self) -> Diacritics {
+        self.compare_type
+    }
+}
+
+impl PartialEq for GreekKey {
+    fn eq(&self, other:&GreekKey) -> bool {
+        self.elements == other.elements
+    }
+}
+
+impl Eq for GreekKey {}
+
+impl Hash for GreekKey {
+    fn hash<H:Hasher>(&self, state:&mut H) {
+        self.elements.hash(state);
+    }
+}
+
+impl PartialOrd for GreekKey {
+    fn partial_cmp(&self, other:&GreekKey) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GreekKey {
+    fn cmp(&self, other:&GreekKey) -> Ordering {
+        self.elements.cmp(&other.elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash(k:&GreekKey) -> u64 {
+        let mut h = DefaultHasher::new();
+        k.hash(&mut h);
+        h.finish()
+    }
+
+    #[test]
+    fn agrees_with_compare() {
+        let words = ["λόγος", "λογος", "ΛΟΓΟΣ", "λόγοσ", "λὸγος", "λόγοι", "ἄνθρωπος", "ἀνθρωπος", "ἄνθρωπος", "\u{EB07}μα",
+            "ᾱ̓́μα", "αμα", "λόγος καί", "λόγος ἔργον", "abc", "abd", "ab", "", "α1", "1α", "ῥήτωρ", "ρητωρ", "ϲ", "σ"];
+        for compare_type in [Diacritics::NONE, Diacritics::ACUTE | Diacritics::GRAVE, Diacritics::ALL] {
+            for a in words {
+                for b in words {
+                    let (ka, kb) = (GreekKey::new(a, compare_type), GreekKey::new(b, compare_type));
+                    assert_eq!(ka.cmp(&kb) as i32, hgk_compare(a, b, compare_type), "{} {} {:?}", a, b, compare_type);
+                    assert_eq!(ka == kb, hash(&ka) == hash(&kb), "{} {} {:?}", a, b, compare_type);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hash_map() {
+        let mut counts:HashMap<GreekKey, usize> = HashMap::new();
+        for w in "Λόγος λόγος λὸγος ΛΟΓΟΣ λόγοι".split(' ') {
+            *counts.entry(GreekKey::new(w, Diacritics::ACUTE | Diacritics::GRAVE)).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&GreekKey::new("λογος", Diacritics::ACUTE | Diacritics::GRAVE)], 4);
+        assert_eq!(counts.keys().find(|k| k.as_str().ends_with('ι')).map(|k| k.compare_type()), Some(Diacritics::ACUTE | Diacritics::GRAVE));
+
+        assert_ne!(GreekKey::new("λόγος", 0), GreekKey::new("λογος", 0));
+    }
+}
@@ -0,0 +1,349 @@
+use alloc::string::String;
+use std::path::Path;
+
+use crate::tables::*;
+use crate::diacritics::Diacritics;
+use crate::hgk_is_combining;
+
+//keyboard layouts map printable ascii keys (0x20 - 0x7E) to greek characters
+const LAYOUT_FIRST_KEY:usize = 0x0020;
+const LAYOUT_LAST_KEY:usize = 0x007E;
+const LAYOUT_NUM_KEYS:usize = LAYOUT_LAST_KEY - LAYOUT_FIRST_KEY + 1;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HgkLayoutPreset {
+    Hoplite,
+    SGreek,
+    BetaCode,
+    ModernGreek
+}
+
+impl HgkLayoutPreset {
+    pub fn from_name(name:&str) -> Option<HgkLayoutPreset> {
+        match name.trim().to_lowercase().as_str() {
+            "hoplite" => Some(HgkLayoutPreset::Hoplite),
+            "sgreek" => Some(HgkLayoutPreset::SGreek),
+            "betacode" | "beta-code" | "beta code" => Some(HgkLayoutPreset::BetaCode),
+            "modern" | "moderngreek" | "modern greek" => Some(HgkLayoutPreset::ModernGreek),
+            _ => None
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum HgkLayoutError {
+    InvalidLine(usize),
+    InvalidKey(usize),
+    InvalidValue(usize),
+    UnknownPreset(usize),
+    Io(std::io::ErrorKind)
+}
+
+/// Maps ascii keys to greek characters, or to diacritics.  '\u{0000}' means the key is not
+/// mapped.  A key is either a letter key or a diacritic key.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct HgkKeyboardLayout {
+    keys: [char; LAYOUT_NUM_KEYS],
+    diacritics: [Diacritics; LAYOUT_NUM_KEYS]
+}
+
+pub const HOPLITE_LAYOUT: HgkKeyboardLayout = HgkKeyboardLayout::preset(HgkLayoutPreset::Hoplite);
+
+impl HgkKeyboardLayout {
+    /// A layout with no keys mapped.
+    pub const fn empty() -> HgkKeyboardLayout {
+        HgkKeyboardLayout { keys: ['\u{0000}'; LAYOUT_NUM_KEYS], diacritics: [Diacritics::NONE; LAYOUT_NUM_KEYS] }
+    }
+
+    pub const fn preset(preset:HgkLayoutPreset) -> HgkKeyboardLayout {
+        let (lower, upper, diacritics) = match preset {
+            HgkLayoutPreset::Hoplite => (GREEK_LOWER, GREEK_UPPER, HOPLITE_DIACRITIC_KEYS),
+            HgkLayoutPreset::SGreek => (SGREEK_LOWER, SGREEK_UPPER, HOPLITE_DIACRITIC_KEYS),
+            HgkLayoutPreset::BetaCode => (BETA_CODE_LOWER, BETA_CODE_UPPER, BETA_CODE_DIACRITIC_KEYS),
+            HgkLayoutPreset::ModernGreek => (MODERN_GREEK_LOWER, MODERN_GREEK_UPPER, MODERN_GREEK_DIACRITIC_KEYS),
+        };
+
+        let mut layout = HgkKeyboardLayout::empty();
+        let mut i = 0;
+        while i < 26 {
+            layout.keys[0x0061 + i - LAYOUT_FIRST_KEY] = lower[i];
+            layout.keys[0x0041 + i - LAYOUT_FIRST_KEY] = upper[i];
+            i += 1;
+        }
+        let mut i = 0;
+        while i < diacritics.len() {
+            layout.diacritics[diacritics[i].0 as usize - LAYOUT_FIRST_KEY] = diacritics[i].1;
+            i += 1;
+        }
+        layout
+    }
+
+    /// Returns the character for key, or None if key is unmapped or not printable ascii.
+    pub fn get(&self, key:char) -> Option<char> {
+        let k = key as usize;
+        if !(LAYOUT_FIRST_KEY..=LAYOUT_LAST_KEY).contains(&k) || self.keys[k - LAYOUT_FIRST_KEY] == '\u{0000}' {
+            None
+        }
+        else {
+            Some(self.keys[k - LAYOUT_FIRST_KEY])
+        }
+    }
+
+    /// Maps key to value, replacing any diacritic on it.  Returns false if key is not printable ascii.
+    pub fn set(&mut self, key:char, value:char) -> bool {
+        let k = key as usize;
+        if !(LAYOUT_FIRST_KEY..=LAYOUT_LAST_KEY).contains(&k) {
+            return false;
+        }
+        self.keys[k - LAYOUT_FIRST_KEY] = value;
+        self.diacritics[k - LAYOUT_FIRST_KEY] = Diacritics::NONE;
+        true
+    }
+
+    /// Returns the diacritics for key, or None if key is not a diacritic key.
+    pub fn diacritic(&self, key:char) -> Option<Diacritics> {
+        let k = key as usize;
+        if !(LAYOUT_FIRST_KEY..=LAYOUT_LAST_KEY).contains(&k) || self.diacritics[k - LAYOUT_FIRST_KEY].is_empty() {
+            None
+        }
+        else {
+            Some(self.diacritics[k - LAYOUT_FIRST_KEY])
+        }
+    }
+
+    /// Makes key a diacritic key for d, replacing any character on it; Diacritics::NONE
+    /// unmaps it.  Returns false if key is not printable ascii.
+    pub fn set_diacritic(&mut self, key:char, d:impl Into<Diacritics>) -> bool {
+        let k = key as usize;
+        if !(LAYOUT_FIRST_KEY..=LAYOUT_LAST_KEY).contains(&k) {
+            return false;
+        }
+        self.keys[k - LAYOUT_FIRST_KEY] = '\u{0000}';
+        self.diacritics[k - LAYOUT_FIRST_KEY] = d.into();
+        true
+    }
+
+    /// The diacritic keys and their diacritics.
+    pub fn diacritic_keys(&self) -> impl Iterator<Item = (char, Diacritics)> + '_ {
+        (LAYOUT_FIRST_KEY..=LAYOUT_LAST_KEY).filter_map(|k| char::from_u32(k as u32)).filter_map(|k| self.diacritic(k).map(|d| (k, d)))
+    }
+
+    /// Same contract as hgk_transliterate: returns '\u{0000}' for unmapped input.
+    pub fn transliterate(&self, input:usize) -> char {
+        if (LAYOUT_FIRST_KEY..=LAYOUT_LAST_KEY).contains(&input) {
+            self.keys[input - LAYOUT_FIRST_KEY]
+        }
+        else {
+            '\u{0000}'
+        }
+    }
+
+    /*
+    Parses a layout from a simple key = value file, which is also valid toml:
+
+    # comments start with #
+    [layout]
+    base = "hoplite"   # optional: start from a preset, otherwise start empty
+    a = "α"
+    "." = "·"
+    v = "\u03DD"
+    ")" = "\u0313"    # combining diacritics make a diacritic key
+    */
+    pub fn from_config(config:&str) -> Result<HgkKeyboardLayout, HgkLayoutError> {
+        let mut layout = HgkKeyboardLayout::empty();
+
+        for (i, raw_line) in config.lines().enumerate() {
+            let line_number = i + 1;
+            let line = strip_comment(raw_line).trim();
+            if line.is_empty() || (line.starts_with('[') && line.ends_with(']')) {
+                continue;
+            }
+
+            let (k, v) = match line.split_once('=') {
+                Some(kv) => kv,
+                None => return Err(HgkLayoutError::InvalidLine(line_number))
+            };
+
+            let key = unquote(k.trim()).ok_or(HgkLayoutError::InvalidKey(line_number))?;
+            let value = unquote(v.trim()).ok_or(HgkLayoutError::InvalidValue(line_number))?;
+
+            if key == "base" {
+                layout = match HgkLayoutPreset::from_name(&value) {
+                    Some(p) => HgkKeyboardLayout::preset(p),
+                    None => return Err(HgkLayoutError::UnknownPreset(line_number))
+                };
+                continue;
+            }
+
+            let mut key_chars = key.chars();
+            let key_char = match (key_chars.next(), key_chars.next()) {
+                (Some(c), None) => c,
+                _ => return Err(HgkLayoutError::InvalidKey(line_number))
+            };
+
+            if !value.is_empty() && value.chars().all(hgk_is_combining) {
+                if !layout.set_diacritic(key_char, value.chars().map(combining_diacritic).collect::<Diacritics>()) {
+                    return Err(HgkLayoutError::InvalidKey(line_number));
+                }
+                continue;
+            }
+
+            let mut value_chars = value.chars();
+            let value_char = match (value_chars.next(), value_chars.next()) {
+                (Some(c), None) => c,
+                (None, _) => '\u{0000}', //empty value unmaps the key
+                _ => return Err(HgkLayoutError::InvalidValue(line_number))
+            };
+
+            if !layout.set(key_char, value_char) {
+                return Err(HgkLayoutError::InvalidKey(line_number));
+            }
+        }
+        Ok(layout)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path:P) -> Result<HgkKeyboardLayout, HgkLayoutError> {
+        let config = std::fs::read_to_string(path).map_err(|e| HgkLayoutError::Io(e.kind()))?;
+        HgkKeyboardLayout::from_config(&config)
+    }
+}
+
+impl Default for HgkKeyboardLayout {
+    fn default() -> HgkKeyboardLayout {
+        HOPLITE_LAYOUT
+    }
+}
+
+//the diacritic of one of the ten combining characters
+pub(crate) fn combining_diacritic(c:char) -> Diacritics {
+    HGK_COMBINING_ORDER.iter().find(|d| d.0 == c).map_or(Diacritics::NONE, |d| d.1)
+}
+
+//a # inside quotes is not a comment
+fn strip_comment(line:&str) -> &str {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '\\' if in_quotes && !escaped => { escaped = true; continue; },
+            '"' if !escaped => in_quotes = !in_quotes,
+            '#' if !in_quotes => return &line[..i],
+            _ => ()
+        }
+        escaped = false;
+    }
+    line
+}
+
+//accepts bare values or double quoted values with \uXXXX, \u{XXXX}, \\ and \" escapes
+fn unquote(s:&str) -> Option<String> {
+    if !s.starts_with('"') {
+        return if s.contains('"') { None } else { Some(String::from(s)) };
+    }
+    if s.len() < 2 || !s.ends_with('"') {
+        return None;
+    }
+
+    let mut res = String::new();
+    let mut chars = s[1..s.len() - 1].chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            if ch == '"' {
+                return None;
+            }
+            res.push(ch);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => res.push('\\'),
+            '"' => res.push('"'),
+            'u' => {
+                let rest = chars.as_str();
+                let (hex, consumed) = if let Some(braced) = rest.strip_prefix('{') {
+                    let end = braced.find('}')?;
+                    (&braced[..end], end + 2)
+                }
+                else {
+                    (rest.get(..4)?, 4)
+                };
+                res.push(char::from_u32(u32::from_str_radix(hex, 16).ok()?)?);
+                chars = rest[consumed..].chars();
+            },
+            _ => return None
+        }
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_presets() {
+        let hoplite = HgkKeyboardLayout::preset(HgkLayoutPreset::Hoplite);
+        assert_eq!(hoplite.transliterate(0x0063), 'ψ');
+        assert_eq!(hoplite.transliterate(0x0071), 'ϝ');
+        assert_eq!(hoplite.transliterate(0x0057), '·');
+        assert_eq!(hoplite.transliterate(0x0031), '\u{0000}');
+        assert_eq!(hoplite.get('1'), None);
+        assert_eq!(hoplite.diacritic('1'), Some(Diacritics::ROUGH));
+        assert_eq!(hoplite.diacritic('a'), None);
+
+        let beta = HgkKeyboardLayout::preset(HgkLayoutPreset::BetaCode);
+        assert_eq!(beta.get('q'), Some('θ'));
+        assert_eq!(beta.get('c'), Some('ξ'));
+        assert_eq!(beta.get('Y'), Some('Ψ'));
+        assert_eq!(beta.diacritic(')'), Some(Diacritics::SMOOTH));
+        assert_eq!(beta.diacritic('|'), Some(Diacritics::IOTA_SUBSCRIPT));
+        assert_eq!(beta.diacritic('1'), None);
+        assert_eq!(beta.diacritic_keys().count(), 8);
+
+        let sgreek = HgkKeyboardLayout::preset(HgkLayoutPreset::SGreek);
+        assert_eq!(sgreek.get('c'), Some('χ'));
+        assert_eq!(sgreek.get('x'), Some('ξ'));
+
+        let modern = HgkKeyboardLayout::preset(HgkLayoutPreset::ModernGreek);
+        assert_eq!(modern.get('q'), Some(';'));
+        assert_eq!(modern.get('u'), Some('θ'));
+        assert_eq!(modern.get('Q'), Some(':'));
+        assert_eq!(modern.diacritic(';'), Some(Diacritics::ACUTE));
+        assert_eq!(modern.diacritic('3'), None);
+
+        let mut layout = HOPLITE_LAYOUT;
+        assert!(layout.set_diacritic('q', Diacritics::MACRON));
+        assert_eq!((layout.get('q'), layout.diacritic('q')), (None, Some(Diacritics::MACRON)));
+        assert!(layout.set('1', 'ϟ'));
+        assert_eq!((layout.get('1'), layout.diacritic('1')), (Some('ϟ'), None));
+        assert!(!layout.set_diacritic('é', Diacritics::ACUTE));
+    }
+
+    #[test]
+    fn layout_config() {
+        let config = "# my layout\n[layout]\nbase = \"betacode\"\nv = \"\\u03DD\" # digamma\nj = \"\\u{03C2}\"\n\"#\" = \"ʹ\"\nx =\n";
+        let layout = HgkKeyboardLayout::from_config(config).unwrap();
+        assert_eq!(layout.get('a'), Some('α'));
+        assert_eq!(layout.get('v'), Some('ϝ'));
+        assert_eq!(layout.get('j'), Some('ς'));
+        assert_eq!(layout.get('#'), Some('ʹ'));
+        assert_eq!(layout.get('x'), None);
+        assert_eq!(layout.diacritic('/'), Some(Diacritics::ACUTE));
+
+        let layout = HgkKeyboardLayout::from_config("base = hoplite\n\"/\" = \"\\u0301\"\n\"&\" = \"\\u0304\\u0313\"\n1 = ϟ").unwrap();
+        assert_eq!(layout.diacritic('/'), Some(Diacritics::ACUTE));
+        assert_eq!(layout.diacritic('&'), Some(Diacritics::MACRON | Diacritics::SMOOTH));
+        assert_eq!(layout.diacritic('1'), None);
+        assert_eq!(layout.get('1'), Some('ϟ'));
+
+        let layout = HgkKeyboardLayout::from_config("a = α\nb = β").unwrap();
+        assert_eq!(layout.get('a'), Some('α'));
+        assert_eq!(layout.get('c'), None);
+
+        assert_eq!(HgkKeyboardLayout::from_config("a = α\nb"), Err(HgkLayoutError::InvalidLine(2)));
+        assert_eq!(HgkKeyboardLayout::from_config("ab = α"), Err(HgkLayoutError::InvalidKey(1)));
+        assert_eq!(HgkKeyboardLayout::from_config("a = αβ"), Err(HgkLayoutError::InvalidValue(1)));
+        assert_eq!(HgkKeyboardLayout::from_config("base = dvorak"), Err(HgkLayoutError::UnknownPreset(1)));
+        assert_eq!(HgkKeyboardLayout::from_file("does-not-exist.toml"), Err(HgkLayoutError::Io(std::io::ErrorKind::NotFound)));
+    }
+}
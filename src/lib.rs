@@ -1,5 +1,8 @@
 //#![no_std]
 #![deny(unsafe_code)]
+//lints the original code is written against
+#![allow(clippy::needless_return, clippy::match_like_matches_macro)]
+#![cfg_attr(test, allow(clippy::single_component_path_imports, clippy::explicit_counter_loop, clippy::useless_conversion, clippy::bool_assert_comparison, unused_parens))]
 
 #[macro_use]
 extern crate alloc;
@@ -8,6 +11,8 @@ use alloc::string::String;
 use std::cmp::Ordering;
 
 use core::cmp;
+use core::str::FromStr;
+use core::ops::Range;
 
 //extern crate tinyvec;
 //use tinyvec::TinyVec;
@@ -15,9 +20,46 @@ use core::cmp;
 //use core::fmt::Display;
 extern crate unicode_normalization;
 use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
 
 pub use crate::tables::*;
 mod tables;
+pub use crate::diacritics::*;
+mod diacritics;
+pub use crate::layout::*;
+mod layout;
+pub use crate::input::*;
+mod input;
+pub use crate::session::*;
+mod session;
+pub use crate::lint::*;
+mod lint;
+pub use crate::pua::*;
+mod pua;
+pub use crate::conformance::*;
+mod conformance;
+pub use crate::numerals::*;
+mod numerals;
+pub use crate::romanize::*;
+mod romanize;
+pub use crate::deromanize::*;
+mod deromanize;
+pub use crate::ipa::*;
+mod ipa;
+pub use crate::monotonic::*;
+mod monotonic;
+pub use crate::report::*;
+mod report;
+pub use crate::homoglyph::*;
+mod homoglyph;
+pub use crate::tokenize::*;
+mod tokenize;
+pub use crate::elision::*;
+mod elision;
+pub use crate::key::*;
+mod key;
+#[cfg(feature = "serde")]
+mod serialize;
 
 const MACRON_AND_SMOOTH:u32 = HGK_MACRON | HGK_SMOOTH;
 const MACRON_AND_SMOOTH_AND_ACUTE:u32 = HGK_MACRON | HGK_SMOOTH | HGK_ACUTE;
@@ -37,10 +79,10 @@ const BREVE_AND_ROUGH_AND_GRAVE:u32 = HGK_BREVE | HGK_ROUGH | HGK_GRAVE;
 const BREVE_AND_ACUTE:u32 = HGK_BREVE | HGK_ACUTE;
 const BREVE_AND_GRAVE:u32 = HGK_BREVE | HGK_GRAVE;
 
-fn get_pua_index(letter:char, diacritics:u32) -> i32 {
+pub(crate) fn get_pua_index(letter:char, diacritics:Diacritics) -> i32 {
     //turn off iota subscript and underdot temporarily 
     //since these are added as combining diacritics later
-    let i = match (diacritics & !HGK_IOTA_SUBSCRIPT) & !HGK_UNDERDOT {
+    let i = match (diacritics - Diacritics::IOTA_SUBSCRIPT - Diacritics::UNDERDOT).bits() {
         MACRON_AND_SMOOTH           => 0,
         MACRON_AND_SMOOTH_AND_ACUTE => 1,
         MACRON_AND_SMOOTH_AND_GRAVE => 2,
@@ -64,45 +106,175 @@ fn get_pua_index(letter:char, diacritics:u32) -> i32 {
         'α' => i,
         'ι' => i + 16,
         'υ' => i + 32,
+        'Α' => i + 48,
+        'Ι' => i + 64,
+        'Υ' => i + 80,
         _ => -1,
     }
 }
 
+//index of alpha with macron and iota subscript in a PuaScheme's encode table, 
+//-1 if there is none.  Only underdot is added as a combining diacritic later.
+pub(crate) fn get_pua_iota_index(letter:char, diacritics:Diacritics) -> i32 {
+    if !diacritics.contains(Diacritics::IOTA_SUBSCRIPT) {
+        return -1;
+    }
+    let i = match (diacritics - Diacritics::IOTA_SUBSCRIPT - Diacritics::UNDERDOT).bits() {
+        HGK_MACRON                  => 0,
+        MACRON_AND_SMOOTH           => 1,
+        MACRON_AND_SMOOTH_AND_ACUTE => 2,
+        MACRON_AND_SMOOTH_AND_GRAVE => 3,
+        MACRON_AND_ROUGH            => 4,
+        MACRON_AND_ROUGH_AND_ACUTE  => 5,
+        MACRON_AND_ROUGH_AND_GRAVE  => 6,
+        MACRON_AND_ACUTE            => 7,
+        MACRON_AND_GRAVE            => 8,
+        _                           => return -1,
+    };
+
+    match letter {
+        'α' => i + 96,
+        'Α' => i + 105,
+        _ => -1,
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HgkLetterType {
     HgkLongVowel,
     HgkShortVowel,
     HgkConsonant
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HgkUnicodeMode {
     Precomposed,
-    CombiningOnly,
-    PrecomposedPUA
+    CombiningOnly,  //marks in a fixed order which suits most fonts
+    PrecomposedPUA,
+    CombiningNFD    //marks in unicode canonical order, the same as nfd()
+}
+
+impl HgkUnicodeMode {
+    /// This mode with PUA characters read and written in pua rather than PUA_SCHEME_DEFAULT.
+    pub const fn with_pua<'a>(self, pua:&'a PuaScheme<'a>) -> HgkOutputMode<'a> {
+        HgkOutputMode { unicode_mode: self, pua }
+    }
 }
 
-#[derive(PartialEq, Debug)]
+/// A unicode mode and the PUA scheme it uses.  Functions which take a mode also take an
+/// HgkOutputMode, e.g. hgk_convert(s, HgkUnicodeMode::PrecomposedPUA.with_pua(&scheme)).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct HgkOutputMode<'a> {
+    pub unicode_mode: HgkUnicodeMode,
+    pub pua: &'a PuaScheme<'a>
+}
+
+impl From<HgkUnicodeMode> for HgkOutputMode<'static> {
+    fn from(unicode_mode:HgkUnicodeMode) -> HgkOutputMode<'static> {
+        unicode_mode.with_pua(&PUA_SCHEME_DEFAULT)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct HGKLetter {
     pub letter: char,
-    pub diacritics: u32
+    pub diacritics: Diacritics,
+    pub marks: HgkMarks
+}
+
+/// Letters sort as in hgk_compare(): non-greek before greek, then by the letter's sort weight
+/// and its diacritics.  Ties, e.g. α and Α or σ and ς, are broken by code point.
+impl Ord for HGKLetter {
+    fn cmp(&self, other:&HGKLetter) -> Ordering {
+        let weight = |l:&HGKLetter| hgk_sort_weight(l.letter).map_or((0, l.letter as u32), |w| (1, w));
+        weight(self).cmp(&weight(other))
+            .then(self.diacritics.cmp(&other.diacritics))
+            .then(self.letter.cmp(&other.letter))
+            .then(self.marks.cmp(&other.marks))
+    }
+}
+
+impl PartialOrd for HGKLetter {
+    fn partial_cmp(&self, other:&HGKLetter) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HgkLetterParseError {
+    Empty,
+    MultipleLetters,
+    LeadingMark   //a combining mark with no letter before it
+}
+
+/// Parse a single letter with its diacritics, precomposed, combining or PUA.
+impl FromStr for HGKLetter {
+    type Err = HgkLetterParseError;
+
+    fn from_str(s:&str) -> Result<HGKLetter, HgkLetterParseError> {
+        let mut letters = s.gkletters();
+        match (s.chars().next(), letters.next(), letters.next()) {
+            (None, _, _) => Err(HgkLetterParseError::Empty),
+            (_, _, Some(_)) => Err(HgkLetterParseError::MultipleLetters),
+            (Some(c), _, _) if is_combining_mark(c) => Err(HgkLetterParseError::LeadingMark),
+            _ => Ok(HGKLetter::from_str(s))
+        }
+    }
 }
 
 pub trait GreekLetters {
-    fn gkletters(&self) -> GreekLetterHolder;
+    fn gkletters(&self) -> GreekLetterHolder<'_>;
+    fn gkletters_pua<'a>(&'a self, pua:&'a PuaScheme<'a>) -> GreekLetterHolder<'a>;
+    fn gkletter_spans(&self) -> GreekLetterSpans<'_>;
+    fn gkletter_spans_pua<'a>(&'a self, pua:&'a PuaScheme<'a>) -> GreekLetterSpans<'a>;
 }
 
 impl GreekLetters for str {
     #[inline]
-    fn gkletters(&self) -> GreekLetterHolder {
+    fn gkletters(&self) -> GreekLetterHolder<'_> {
         new_gkletters(self)
     }
+
+    #[inline]
+    fn gkletters_pua<'a>(&'a self, pua:&'a PuaScheme<'a>) -> GreekLetterHolder<'a> {
+        new_gkletters_pua(self, pua)
+    }
+
+    #[inline]
+    fn gkletter_spans(&self) -> GreekLetterSpans<'_> {
+        GreekLetterSpans { letters: new_gkletters(self) }
+    }
+
+    #[inline]
+    fn gkletter_spans_pua<'a>(&'a self, pua:&'a PuaScheme<'a>) -> GreekLetterSpans<'a> {
+        GreekLetterSpans { letters: new_gkletters_pua(self, pua) }
+    }
+}
+
+/// Like gkletters(), but also yields the byte range of each letter in the string.
+#[derive(Clone, Debug)]
+pub struct GreekLetterSpans<'a> {
+    letters: GreekLetterHolder<'a>
+}
+
+impl<'a> Iterator for GreekLetterSpans<'a> {
+    type Item = (Range<usize>, HGKLetter);
+
+    #[inline]
+    fn next(&mut self) -> Option<(Range<usize>, HGKLetter)> {
+        let start = self.letters.cursor.cur_cursor();
+        let letter = self.letters.next()?;
+        Some((start..self.letters.cursor.cur_cursor(), letter))
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct GreekLetterHolder<'a> {
     string: &'a str,
-    cursor: GreekLetterCursor,
-    cursor_back: GreekLetterCursor,
+    cursor: GreekLetterCursor<'a>,
+    cursor_back: GreekLetterCursor<'a>,
 }
 /*
 impl<'a> GreekLetterHolder<'a> {
@@ -150,19 +322,28 @@ impl<'a> DoubleEndedIterator for GreekLetterHolder<'a> {
 }
 
 #[inline]
-pub fn new_gkletters(s: &str) -> GreekLetterHolder {
+pub fn new_gkletters(s: &str) -> GreekLetterHolder<'_> {
+    new_gkletters_pua(s, &PUA_SCHEME_DEFAULT)
+}
+
+#[inline]
+pub fn new_gkletters_pua<'a>(s: &'a str, pua: &'a PuaScheme<'a>) -> GreekLetterHolder<'a> {
     let len = s.len();
     GreekLetterHolder {
         string: s,
-        cursor: GreekLetterCursor::new(0, len),
-        cursor_back: GreekLetterCursor::new(len, len),
+        cursor: GreekLetterCursor::new_with_pua(0, len, pua),
+        cursor_back: GreekLetterCursor::new_with_pua(len, len, pua),
     }
 }
 
 #[derive(Clone, Debug)]
-pub struct GreekLetterCursor {
+pub struct GreekLetterCursor<'a> {
     offset: usize,
-    len: usize
+    len: usize,
+    pua: &'a PuaScheme<'a>,
+    //set by prev_boundary() when it stops inside a run of marks: the number of unknown marks
+    //between the run's base and offset, so the next call need not scan back to the base
+    marks_before: Option<usize>
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -170,11 +351,20 @@ pub enum GreekLetterError {
     InvalidOffset
 }
 
-impl GreekLetterCursor {
-    pub fn new(offset: usize, len: usize) -> GreekLetterCursor {
+impl GreekLetterCursor<'static> {
+    pub fn new(offset: usize, len: usize) -> GreekLetterCursor<'static> {
+        GreekLetterCursor::new_with_pua(offset, len, &PUA_SCHEME_DEFAULT)
+    }
+}
+
+impl<'a> GreekLetterCursor<'a> {
+    /// A cursor which decodes private use area characters with the given scheme.
+    pub fn new_with_pua(offset: usize, len: usize, pua: &'a PuaScheme<'a>) -> GreekLetterCursor<'a> {
         GreekLetterCursor {
             offset,
-            len
+            len,
+            pua,
+            marks_before: None
         }
     }
 
@@ -183,6 +373,7 @@ impl GreekLetterCursor {
     pub fn set_cursor(&mut self, offset: usize) {
         if offset != self.offset {
             self.offset = offset;
+            self.marks_before = None;
         }
     }
 
@@ -202,15 +393,18 @@ impl GreekLetterCursor {
             //return Ok(None);
         }
 
+        self.marks_before = None;
         let mut the_letter = '\u{0000}';
         let mut diacritics:u32 = 0;
+        let mut marks = HgkMarks::NONE;
 
         let mut iter = chunk[self.offset - chunk_start..].chars(); //nfd()
         let mut ch = iter.next().unwrap();
         //println!("next boundary: offset: {} {}", self.offset, ch);
         
         loop {
-                if the_letter == '\u{0000}' && !hgk_is_combining(ch) {
+                //a mark with no letter before it is a letter of its own
+                if the_letter == '\u{0000}' {
                     if ch as u32 >= 0x0370 && ch as u32 <= 0x03FF {
                         //basic greek conversion
                         the_letter = GREEK_BASIC[ch as usize - 0x0370].0;
@@ -228,19 +422,17 @@ impl GreekLetterCursor {
                             the_letter = ch;
                         }
                     }
-                    else if ch as u32 >= 0xEAF0 && ch as u32 <= 0xEB8A {
+                    else if let Some((l, d)) = self.pua.decode(ch) {
                         //PUA conversion
-                        the_letter = GREEK_PUA[ch as usize - 0xEAF0].0;
-                        diacritics |= GREEK_PUA[ch as usize - 0xEAF0].1;
-                        if the_letter == NOT_ACCENTABLE_CHAR || the_letter == '\u{0000}' {
-                            the_letter = ch;
-                        }
+                        the_letter = l;
+                        diacritics |= d.bits();
                     }
                     else {
                        the_letter = ch;
                     }
                 }
                 else if hgk_is_combining(ch) {
+                    let seen = diacritics;
                     match ch {
                         '\u{0300}' => diacritics |= HGK_GRAVE,
                         '\u{0301}' => diacritics |= HGK_ACUTE,
@@ -254,11 +446,16 @@ impl GreekLetterCursor {
                         '\u{0345}' => diacritics |= HGK_IOTA_SUBSCRIPT,
                         _ => {}
                     }
+                    //marks before it are written before it
+                    marks.diacritic_after(Diacritics::from_bits_truncate(diacritics & !seen));
+                }
+                else if is_combining_mark(ch) && marks.push(ch) {
+                    //other combining marks are kept with the letter
                 }
                 else {
                     //self.offset += ch.len_utf8();
                     //else boundary character, return
-                    return Ok(Some(HGKLetter{letter:the_letter, diacritics}));
+                    return Ok(Some(HGKLetter{letter:the_letter, diacritics:Diacritics::from_bits_truncate(diacritics), marks}));
                 }
 
                 self.offset += ch.len_utf8();
@@ -269,7 +466,7 @@ impl GreekLetterCursor {
                     //at the end
                     //println!("herehere2: {}", self.offset);
                     //return Ok(None);
-                    return Ok(Some(HGKLetter{letter:the_letter, diacritics}));
+                    return Ok(Some(HGKLetter{letter:the_letter, diacritics:Diacritics::from_bits_truncate(diacritics), marks}));
                 }
                 else {
                     return Ok(None);
@@ -278,6 +475,9 @@ impl GreekLetterCursor {
         }
     
     #[inline]
+    /// Letters are found by going back to the nearest base character and counting the marks
+    /// after it, so that they are the same as those found by next_boundary().  Walking back
+    /// over a long run of marks does not rescan the run for each letter.
     pub fn prev_boundary(&mut self, chunk: &str, chunk_start: usize) -> Result<Option<HGKLetter>, GreekLetterError> {
 
         if self.offset == 0 {
@@ -285,96 +485,76 @@ impl GreekLetterCursor {
             //return Ok(None);
         }
 
-        let mut the_letter = '\u{0000}';
-        let mut diacritics:u32 = 0;
-
-        let mut iter = chunk[..self.offset - chunk_start].chars().rev(); //nfd()
-        let mut ch = iter.next().unwrap();
-        
-        loop {
-                if the_letter == '\u{0000}' && !hgk_is_combining(ch) {
-                    if ch as u32 >= 0x0370 && ch as u32 <= 0x03FF {
-                        //basic greek conversion
-                        the_letter = GREEK_BASIC[ch as usize - 0x0370].0;
-                        diacritics |= GREEK_BASIC[ch as usize - 0x0370].1;
-
-                        if the_letter == NOT_ACCENTABLE_CHAR || the_letter == '\u{0000}' {
-                            the_letter = ch;
-                        }
-                    }
-                    else if ch as u32 >= 0x1F00 && ch as u32 <= 0x1FFF {
-                        //extended greek conversion
-                        the_letter = GREEK_EXTENDED[ch as usize - 0x1F00].0;
-                        diacritics |= GREEK_EXTENDED[ch as usize - 0x1F00].1;
-                        if the_letter == NOT_ACCENTABLE_CHAR || the_letter == '\u{0000}' {
-                            the_letter = ch;
-                        }
-                    }
-                    else if ch as u32 >= 0xEAF0 && ch as u32 <= 0xEB8A {
-                        //PUA conversion
-                        the_letter = GREEK_PUA[ch as usize - 0xEAF0].0;
-                        diacritics |= GREEK_PUA[ch as usize - 0xEAF0].1;
-                        if the_letter == NOT_ACCENTABLE_CHAR || the_letter == '\u{0000}' {
-                            the_letter = ch;
-                        }
+        let before = &chunk[..self.offset - chunk_start];
+        let is_unknown_mark = |c:char| is_combining_mark(c) && !hgk_is_combining(c);
+
+        //after the base, every HGK_MAX_MARKS + 1th unknown mark starts a letter, so count them
+        let marks_before = match self.marks_before.take() {
+            Some(n) => n,
+            None => {
+                let mut n = 0;
+                let mut base_is_mark = false;
+                for ch in before.chars().rev() {
+                    if !is_combining_mark(ch) {
+                        base_is_mark = false;
+                        break;
                     }
-                    else {
-                       the_letter = ch;
+                    base_is_mark = is_unknown_mark(ch);
+                    if base_is_mark {
+                        n += 1;
                     }
-
-                    //found letter: move offset and return
-                    self.offset -= ch.len_utf8();
-                    return Ok(Some(HGKLetter{letter:the_letter, diacritics}));
                 }
-                else if hgk_is_combining(ch) {
-                    match ch {
-                        '\u{0300}' => diacritics |= HGK_GRAVE,
-                        '\u{0301}' => diacritics |= HGK_ACUTE,
-                        '\u{0304}' => diacritics |= HGK_MACRON,
-                        '\u{0306}' => diacritics |= HGK_BREVE,
-                        '\u{0308}' => diacritics |= HGK_DIAERESIS,
-                        '\u{0313}' => diacritics |= HGK_SMOOTH,
-                        '\u{0314}' => diacritics |= HGK_ROUGH,
-                        '\u{0323}' => diacritics |= HGK_UNDERDOT,
-                        '\u{0342}' => diacritics |= HGK_CIRCUMFLEX,
-                        '\u{0345}' => diacritics |= HGK_IOTA_SUBSCRIPT,
-                        _ => {}
-                    }
-                }
-                /*
-                is this even reachable??
-                else {
-                    //self.offset += ch.len_utf8();
-                    //else boundary character, return
-                    return Ok(Some(HGKLetter{letter:the_letter, diacritics}));
-                }*/
-                self.offset -= ch.len_utf8();
-
-                if let Some(next_ch) = iter.next() {      
-                    ch = next_ch;
+                //a mark at the start of the chunk is the base
+                if base_is_mark { n - 1 } else { n }
+            }
+        };
 
-                } else if self.offset == 0 {
-                    //at the end
-                    //println!("herehere2: {} {}", self.offset, diacritics);
-                    //return Ok(None);
-                    return Ok(Some(HGKLetter{letter:the_letter, diacritics}));
+        let mut start = self.offset;
+        let marks_in_letter = marks_before % (HGK_MAX_MARKS + 1);
+        if marks_before > HGK_MAX_MARKS {
+            //the letter starts with an unknown mark; scan back only to it
+            let mut n = 0;
+            for ch in before.chars().rev() {
+                start -= ch.len_utf8();
+                if is_unknown_mark(ch) {
+                    if n == marks_in_letter {
+                        break;
+                    }
+                    n += 1;
                 }
-                else {
-                    return Ok(None);
+            }
+            self.marks_before = Some(marks_before - marks_in_letter - 1);
+        }
+        else {
+            for ch in before.chars().rev() {
+                start -= ch.len_utf8();
+                if !is_combining_mark(ch) {
+                    break;
                 }
-            }    
+            }
         }
 
+        let mut forward = GreekLetterCursor::new_with_pua(start, self.offset, self.pua);
+        let letter = forward.next_boundary(before, chunk_start)?;
+        debug_assert_eq!(forward.offset, self.offset);
+        self.offset = start;
+        Ok(letter)
+    }
+
 }
 /************************************************/
 
 impl HGKLetter {
+    pub const fn new(letter:char, diacritics:Diacritics) -> HGKLetter {
+        HGKLetter { letter, diacritics, marks: HgkMarks::NONE }
+    }
+
     pub fn letter_type(&self) -> HgkLetterType {
         if self.letter.is_long() {
             return HgkLetterType::HgkLongVowel;
         }
         else if self.letter.is_long_or_short() {
-            if (self.diacritics & HGK_MACRON) == HGK_MACRON {
+            if self.diacritics.contains(Diacritics::MACRON) {
                 return HgkLetterType::HgkLongVowel;
             }
             else {
@@ -390,8 +570,13 @@ impl HGKLetter {
     }
 
     fn from_str(l:&str) -> HGKLetter {
+        HGKLetter::from_str_pua(l, &PUA_SCHEME_DEFAULT)
+    }
+
+    fn from_str_pua(l:&str, pua:&PuaScheme) -> HGKLetter {
         let mut diacritics:u32 = 0;
         let mut the_letter: char = '\u{0000}';
+        let mut marks = HgkMarks::NONE;
         for (i, ch) in l.chars().enumerate() {
             if i == 0 {
                 assert!( !hgk_is_combining(ch) ); //"First char of letter is a combining mark."); just ignore it?
@@ -410,16 +595,17 @@ impl HGKLetter {
                     the_letter = GREEK_EXTENDED[ch as usize - 0x1F00].0;
                     diacritics = GREEK_EXTENDED[ch as usize - 0x1F00].1;
                 }
-                else if ch as u32 >= 0xEAF0 && ch as u32 <= 0xEB8A {
+                else if let Some((l, d)) = pua.decode(ch) {
                     //PUA conversion
-                    the_letter = GREEK_PUA[ch as usize - 0xEAF0].0;
-                    diacritics = GREEK_PUA[ch as usize - 0xEAF0].1;
+                    the_letter = l;
+                    diacritics = d.bits();
                 }
                 else {
                     the_letter = ch;
                 }                
             }
             else {
+                let seen = diacritics;
                 match ch {
                     '\u{0300}' => diacritics |= HGK_GRAVE,
                     '\u{0301}' => diacritics |= HGK_ACUTE,
@@ -431,12 +617,13 @@ impl HGKLetter {
                     '\u{0323}' => diacritics |= HGK_UNDERDOT,
                     '\u{0342}' => diacritics |= HGK_CIRCUMFLEX,
                     '\u{0345}' => diacritics |= HGK_IOTA_SUBSCRIPT,
-                    _ => break
+                    _ => if !is_combining_mark(ch) || !marks.push(ch) { break }
                 }
+                marks.diacritic_after(Diacritics::from_bits_truncate(diacritics & !seen));
             }
         }
         
-        HGKLetter { letter: the_letter, diacritics }
+        HGKLetter { letter: the_letter, diacritics: Diacritics::from_bits_truncate(diacritics), marks }
     }
 /*
 order:
@@ -451,52 +638,32 @@ COMBINING_CIRCUMFLEX,
 COMBINING_IOTA_SUBSCRIPT, 
 COMBINING_UNDERDOT
 */
-    pub fn to_string(&self, unicode_mode:HgkUnicodeMode) -> String {
+    pub fn to_string<'a>(&self, unicode_mode:impl Into<HgkOutputMode<'a>>) -> String {
+        let mode = unicode_mode.into();
+        self.to_string_pua(mode.unicode_mode, mode.pua)
+    }
+
+    /// Like to_string, but PrecomposedPUA output uses the given PUA scheme.
+    pub fn to_string_pua(&self, unicode_mode:HgkUnicodeMode, pua:&PuaScheme) -> String {
         let mut s = vec![self.letter];
-        if (self.diacritics & HGK_MACRON) == HGK_MACRON {
-            s.push('\u{0304}');
-        }
-        if (self.diacritics & HGK_BREVE) == HGK_BREVE {
-            s.push('\u{0306}');
-        }
-        if (self.diacritics & HGK_DIAERESIS) == HGK_DIAERESIS {
-            s.push('\u{0308}');
-        }
-        if (self.diacritics & HGK_ROUGH) == HGK_ROUGH {
-            s.push('\u{0314}');
-        }
-        if (self.diacritics & HGK_SMOOTH) == HGK_SMOOTH {
-            s.push('\u{0313}');
-        }    
-        if (self.diacritics & HGK_ACUTE) == HGK_ACUTE {
-            s.push('\u{0301}');
-        }
-        if (self.diacritics & HGK_GRAVE) == HGK_GRAVE {
-            s.push('\u{0300}');
-        }
-        if (self.diacritics & HGK_CIRCUMFLEX) == HGK_CIRCUMFLEX {
-            s.push('\u{0342}');
-        }
-        if (self.diacritics & HGK_IOTA_SUBSCRIPT) == HGK_IOTA_SUBSCRIPT {
-            s.push('\u{0345}');
-        }
-        if (self.diacritics & HGK_UNDERDOT) == HGK_UNDERDOT {
-            s.push('\u{0323}');
-        }
+        self.push_combining(self.diacritics, &mut s);
         match unicode_mode {
             HgkUnicodeMode::CombiningOnly => s.into_iter().collect::<String>(),
+            HgkUnicodeMode::CombiningNFD => s.into_iter().nfd().collect::<String>(),
             HgkUnicodeMode::PrecomposedPUA => {
-                let idx = get_pua_index(self.letter, self.diacritics);
-                if (0..=GREEK_LOWER_PUA.len() as i32 - 1 ).contains(&idx) {
+                //a PUA character can only be used if no mark was written among its diacritics
+                let usable = |covered:Diacritics| self.marks.iter_placed().all(|(_, ahead)| !ahead.intersects(covered));
+                let covered = self.diacritics - Diacritics::UNDERDOT;
+                if let Some(pua_char) = pua.encode_iota(self.letter, self.diacritics).filter(|_| usable(covered)) {
                     s.clear();
-                    s.push( GREEK_LOWER_PUA[idx as usize] );
-
-                    if (self.diacritics & HGK_IOTA_SUBSCRIPT) == HGK_IOTA_SUBSCRIPT {
-                        s.push('\u{0345}');
-                    }
-                    if (self.diacritics & HGK_UNDERDOT) == HGK_UNDERDOT {
-                        s.push('\u{0323}');
-                    }
+                    s.push( pua_char );
+                    self.push_combining(Diacritics::UNDERDOT & self.diacritics, &mut s);
+                    s.into_iter().collect::<String>() 
+                }
+                else if let Some(pua_char) = pua.encode(self.letter, self.diacritics).filter(|_| usable(covered - Diacritics::IOTA_SUBSCRIPT)) {
+                    s.clear();
+                    s.push( pua_char );
+                    self.push_combining((Diacritics::IOTA_SUBSCRIPT | Diacritics::UNDERDOT) & self.diacritics, &mut s);
                     s.into_iter().collect::<String>() 
                 }
                 else {
@@ -507,78 +674,74 @@ COMBINING_UNDERDOT
         }  
     }
 
-    pub fn toggle_diacritic(&mut self, d:u32, on_only:bool) {
-        if !self.is_legal(d) {
-            return;
+    //the combining characters for diacritics d, with the marks among them where they were written
+    fn push_combining(&self, d:Diacritics, s:&mut Vec<char>) {
+        let mut marks = self.marks.iter_placed().peekable();
+        for (c, diacritic) in HGK_COMBINING_ORDER {
+            if d.contains(*diacritic) {
+                while let Some((mark, _)) = marks.next_if(|(_, ahead)| ahead.contains(*diacritic)) {
+                    s.push(mark);
+                }
+                s.push(*c);
+            }
         }
+        s.extend(marks.map(|(mark, _)| mark));
+    }
+
+    /// Toggles the single diacritic d, turning off any which clash with it.  A diacritic
+    /// which may not go on this letter, or more than one at once, is ignored.
+    pub fn toggle_diacritic(&mut self, d:impl Into<Diacritics>, on_only:bool) {
+        let d = d.into();
+        debug_assert!(d.len() <= 1, "toggle_diacritic takes one diacritic");
+        let _ = self.try_toggle_diacritic(d, on_only);
+    }
 
-        if self.diacritics & d != d || on_only {
+    /// Like toggle_diacritic, but reports what happened: whether d was applied, removed
+    /// or rejected, and which clashing diacritics were turned off.
+    pub fn try_toggle_diacritic(&mut self, d:Diacritics, on_only:bool) -> HgkToggleResult {
+        if let Some(reason) = self.reject_reason(d) {
+            return HgkToggleResult::Rejected(reason);
+        }
+
+        let applied = !self.diacritics.contains(d) || on_only;
+        if applied {
             self.diacritics |= d;
         }
         else {
-            self.diacritics &= !d; //turn off: rust uses !, C uses ~
-            //return;
+            self.diacritics -= d;
         }
 
         //turn off clashing diacritics:
-        match d {
-            HGK_ROUGH => {
-                self.diacritics &= !(HGK_SMOOTH | HGK_DIAERESIS);
-            },
-            HGK_SMOOTH => {
-                self.diacritics &= !(HGK_ROUGH | HGK_DIAERESIS);
-            },
-            HGK_ACUTE => {
-                self.diacritics &= !(HGK_GRAVE | HGK_CIRCUMFLEX);
-            },
-            HGK_GRAVE => {
-                self.diacritics &= !(HGK_ACUTE | HGK_CIRCUMFLEX);
-            },
-            HGK_CIRCUMFLEX => {
-                self.diacritics &= !(HGK_ACUTE | HGK_GRAVE | HGK_MACRON | HGK_BREVE);
-            },
-            HGK_MACRON => {
-                self.diacritics &= !(HGK_BREVE | HGK_CIRCUMFLEX);
-            },
-            HGK_BREVE => {
-                self.diacritics &= !(HGK_MACRON | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT);
-            },
-            HGK_IOTA_SUBSCRIPT => {
-                self.diacritics &= !(HGK_BREVE);
-            },
-            HGK_DIAERESIS => {
-                self.diacritics &= !(HGK_ROUGH | HGK_SMOOTH);
-            },
-            HGK_UNDERDOT => { },
-            _ => {
-                debug_assert!(false, "Unknown Diacritic passed")
-            }
+        let conflicts = self.diacritics & hgk_clashing_diacritics(d);
+        self.diacritics -= conflicts;
+
+        if applied {
+            HgkToggleResult::Applied(conflicts)
+        }
+        else {
+            HgkToggleResult::Removed(conflicts)
         }
     }
 
-    fn is_legal(&mut self, d:u32) -> bool {
-        match d {
-            HGK_ROUGH => {
-                self.letter.is_greek_vowel() || self.letter == 'ρ' || self.letter == 'Ρ'
-            },
-            HGK_SMOOTH => {
+    pub(crate) fn is_legal(&self, d:Diacritics) -> bool {
+        self.reject_reason(d).is_none()
+    }
+
+    /// Returns None if the single diacritic d may be put on this letter, else the reason it may not.
+    pub fn reject_reason(&self, d:Diacritics) -> Option<HgkRejectReason> {
+        let legal = match d.bits() {
+            HGK_ROUGH | HGK_SMOOTH => {
                 self.letter.is_greek_vowel() || self.letter == 'ρ' || self.letter == 'Ρ'
             },
-            HGK_ACUTE => {
-                self.letter.is_greek_vowel()
-            },
-            HGK_GRAVE => {
+            HGK_ACUTE | HGK_GRAVE => {
                 self.letter.is_greek_vowel()
             },
             HGK_CIRCUMFLEX => {
                 self.letter.is_long_or_short() || self.letter.is_long()
             },
-            HGK_MACRON => {
+            HGK_MACRON | HGK_BREVE => {
                 self.letter.is_long_or_short()
             },
-            HGK_BREVE => {
-                self.letter.is_long_or_short()    
-            },
             HGK_IOTA_SUBSCRIPT => {
                 matches!(self.letter, 'α' | 'ω' | 'η') 
             },
@@ -588,10 +751,56 @@ COMBINING_UNDERDOT
             HGK_UNDERDOT => { 
                 true
             },
-            _ => false
+            _ => return Some(HgkRejectReason::UnknownDiacritic)
+        };
+
+        if legal {
+            return None;
+        }
+        Some(match d.bits() {
+            HGK_ROUGH | HGK_SMOOTH => HgkRejectReason::BreathingNotOnVowelOrRho,
+            HGK_ACUTE | HGK_GRAVE => HgkRejectReason::AccentNotOnVowel,
+            HGK_CIRCUMFLEX => HgkRejectReason::CircumflexNotOnLongVowel,
+            HGK_MACRON | HGK_BREVE => HgkRejectReason::LengthNotOnAlphaIotaUpsilon,
+            HGK_IOTA_SUBSCRIPT => HgkRejectReason::IotaSubscriptNotOnAlphaEtaOmega,
+            _ => HgkRejectReason::DiaeresisNotOnIotaUpsilon
+        })
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HgkRejectReason {
+    BreathingNotOnVowelOrRho,
+    AccentNotOnVowel,
+    CircumflexNotOnLongVowel,
+    LengthNotOnAlphaIotaUpsilon,
+    IotaSubscriptNotOnAlphaEtaOmega,
+    DiaeresisNotOnIotaUpsilon,
+    UnknownDiacritic
+}
+
+impl HgkRejectReason {
+    pub fn description(&self) -> &'static str {
+        match self {
+            HgkRejectReason::BreathingNotOnVowelOrRho => "breathings only on vowels and ρ",
+            HgkRejectReason::AccentNotOnVowel => "acute and grave only on vowels",
+            HgkRejectReason::CircumflexNotOnLongVowel => "circumflex only on α/ι/υ/η/ω",
+            HgkRejectReason::LengthNotOnAlphaIotaUpsilon => "macron and breve only on α/ι/υ",
+            HgkRejectReason::IotaSubscriptNotOnAlphaEtaOmega => "iota subscript only on α/η/ω",
+            HgkRejectReason::DiaeresisNotOnIotaUpsilon => "diaeresis only on ι/υ",
+            HgkRejectReason::UnknownDiacritic => "not a single diacritic"
         }
     }
 }
+
+//conflicts are the clashing diacritics which were turned off
+#[derive(PartialEq, Eq, Debug)]
+pub enum HgkToggleResult {
+    Applied(Diacritics),
+    Removed(Diacritics),
+    Rejected(HgkRejectReason)
+}
+
 /*
 //https://doc.rust-lang.org/stable/rust-by-example/conversion/string.html
 impl Display for HGKLetter {
@@ -600,6 +809,26 @@ impl Display for HGKLetter {
     }
 }
 */
+//the diacritics which are turned off when d is turned on
+pub(crate) fn hgk_clashing_diacritics(d:Diacritics) -> Diacritics {
+    Diacritics::from_bits_truncate(match d.bits() {
+        HGK_ROUGH => HGK_SMOOTH | HGK_DIAERESIS,
+        HGK_SMOOTH => HGK_ROUGH | HGK_DIAERESIS,
+        HGK_ACUTE => HGK_GRAVE | HGK_CIRCUMFLEX,
+        HGK_GRAVE => HGK_ACUTE | HGK_CIRCUMFLEX,
+        HGK_CIRCUMFLEX => HGK_ACUTE | HGK_GRAVE | HGK_MACRON | HGK_BREVE,
+        HGK_MACRON => HGK_BREVE | HGK_CIRCUMFLEX,
+        HGK_BREVE => HGK_MACRON | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT,
+        HGK_IOTA_SUBSCRIPT => HGK_BREVE,
+        HGK_DIAERESIS => HGK_ROUGH | HGK_SMOOTH,
+        HGK_UNDERDOT => HGK_NO_DIACRITICS,
+        _ => {
+            debug_assert!(false, "Unknown Diacritic passed");
+            HGK_NO_DIACRITICS
+        }
+    })
+}
+
 pub trait HGKIsLong {
     fn is_long(&self) -> bool;
 }
@@ -641,40 +870,45 @@ impl HGKIsGreekVowel for char {
     }
 }
 
-pub fn hgk_strip_diacritics(l:&str, turnoff_diacritics:u32) -> String {
+pub fn hgk_strip_diacritics(l:&str, turnoff_diacritics:impl Into<Diacritics>) -> String {
     //let b = l.gkletters();
     //println!("num: {}", b.collect::<Vec<HGKLetter>>().len() );
-    l.gkletters().map(|a| HGKLetter{letter:a.letter, diacritics:a.diacritics & !turnoff_diacritics}.to_string(HgkUnicodeMode::Precomposed)).collect::<String>()
+    let turnoff_diacritics = turnoff_diacritics.into();
+    l.gkletters().map(|a| HGKLetter{letter:a.letter, diacritics:a.diacritics - turnoff_diacritics, marks:a.marks}.to_string(HgkUnicodeMode::Precomposed)).collect::<String>()
 }
 
-pub fn hgk_strip_diacritics_and_replace_circumflex_with_macron(l:&str, turnoff_diacritics:u32) -> String {
+pub fn hgk_strip_diacritics_and_replace_circumflex_with_macron(l:&str, turnoff_diacritics:impl Into<Diacritics>) -> String {
     //let b = l.gkletters();
     //println!("num: {}", b.collect::<Vec<HGKLetter>>().len() );
-    l.gkletters().map(|a| {let d = if (a.diacritics & HGK_CIRCUMFLEX ) == HGK_CIRCUMFLEX && (a.letter == 'ι' ) { a.diacritics | HGK_MACRON } else {a.diacritics}; HGKLetter{letter:a.letter, diacritics: d & !turnoff_diacritics}.to_string(HgkUnicodeMode::Precomposed)}).collect::<String>()
+    let turnoff_diacritics = turnoff_diacritics.into();
+    l.gkletters().map(|a| {let d = if a.diacritics.contains(Diacritics::CIRCUMFLEX) && (a.letter == 'ι' ) { a.diacritics | Diacritics::MACRON } else {a.diacritics}; HGKLetter{letter:a.letter, diacritics: d - turnoff_diacritics, marks:a.marks}.to_string(HgkUnicodeMode::Precomposed)}).collect::<String>()
 }
 
 //returns true if one or more of the bits in check_diacritics is/are set
-pub fn hgk_has_diacritics(l:&str, check_diacritics:u32) -> bool {
+pub fn hgk_has_diacritics(l:&str, check_diacritics:impl Into<Diacritics>) -> bool {
+    let check_diacritics = check_diacritics.into();
     //let b = l.gkletters();
     //println!("num: {}", b.collect::<Vec<HGKLetter>>().len() );
     
     //turn off all other bits, see if it equals 0 or not
     for a in l.gkletters() { //.map(|a| HGKLetter{letter:a.letter, diacritics:a.diacritics & !turnoff_diacritics}.to_string(HgkUnicodeMode::PrecomposedPUA)).collect::<String>()
-        if (a.diacritics & check_diacritics) != 0 {
+        if a.diacritics.intersects(check_diacritics) {
             return true;
         }
     }
     false
 }
 
-pub fn hgk_convert(l:&str, mode:HgkUnicodeMode) -> String {
+pub fn hgk_convert<'a>(l:&str, mode:impl Into<HgkOutputMode<'a>>) -> String {
     //let b = l.gkletters();
     //println!("num: {}", b.collect::<Vec<HGKLetter>>().len() );
-    l.gkletters().map(|a| a.to_string(mode)).collect::<String>()
+    let mode = mode.into();
+    l.gkletters_pua(mode.pua).map(|a| a.to_string(mode)).collect::<String>()
 }
 
-pub fn hgk_toggle_diacritic_str(l:&str, d:u32, on_only:bool, mode:HgkUnicodeMode) -> String {
-    let mut letter = HGKLetter::from_str(l);
+pub fn hgk_toggle_diacritic_str<'a>(l:&str, d:impl Into<Diacritics>, on_only:bool, mode:impl Into<HgkOutputMode<'a>>) -> String {
+    let mode = mode.into();
+    let mut letter = HGKLetter::from_str_pua(l, mode.pua);
     letter.toggle_diacritic(d, on_only);
     letter.to_string(mode)
 }
@@ -687,8 +921,14 @@ pub fn hgk_compare_sqlite(s1: &str, s2: &str) -> Ordering {
     }
 }
 
+//the GREEK_BASIC sort weight of a letter in the greek block, None outside it
+pub(crate) fn hgk_sort_weight(c:char) -> Option<u32> {
+    (0x0370..=0x03FF).contains(&(c as usize)).then(|| GREEK_BASIC[c as usize - 0x0370].2)
+}
+
 //set compare_type to 0xFFFF for diacritic insensitive
-pub fn hgk_compare(a:&str, b:&str, compare_type:u32) -> i32 {
+pub fn hgk_compare(a:&str, b:&str, compare_type:impl Into<Diacritics>) -> i32 {
+    let compare_type = compare_type.into();
     let mut a1 = a.gkletters();
     let mut b1 = b.gkletters();
 
@@ -720,7 +960,7 @@ pub fn hgk_compare(a:&str, b:&str, compare_type:u32) -> i32 {
             match lettera.cmp(&letterb) {
                  Ordering::Less => return -1,
                  Ordering::Greater => return 1,
-                 Ordering::Equal => return 0
+                 Ordering::Equal => continue
             }
         }
         else if !(0x0370..=0x03FF).contains(&lettera) { //non-greek sorts before greek 
@@ -740,8 +980,8 @@ pub fn hgk_compare(a:&str, b:&str, compare_type:u32) -> i32 {
              Ordering::Equal => ()
         }
 
-        if (a_letter.as_ref().unwrap().diacritics & !compare_type) != (b_letter.as_ref().unwrap().diacritics & !compare_type) {
-            if (a_letter.unwrap().diacritics & !compare_type) < (b_letter.unwrap().diacritics & !compare_type) {
+        if (a_letter.as_ref().unwrap().diacritics - compare_type) != (b_letter.as_ref().unwrap().diacritics - compare_type) {
+            if (a_letter.unwrap().diacritics - compare_type) < (b_letter.unwrap().diacritics - compare_type) {
                 return -1;
             }
             else {
@@ -780,15 +1020,11 @@ pub fn hgk_is_combining(c:char) -> bool {
 }
 
 pub fn hgk_transliterate(input:usize) -> char {
-    if (0x0061..=0x007A).contains(&input) {
-        GREEK_LOWER[input - 0x0061]
-    }
-    else if (0x0041..=0x005A).contains(&input) {
-        GREEK_UPPER[input - 0x0041]
-    }
-    else {
-        '\u{0000}'
-    }
+    HOPLITE_LAYOUT.transliterate(input)
+}
+
+pub fn hgk_transliterate_with_layout(input:usize, layout:&HgkKeyboardLayout) -> char {
+    layout.transliterate(input)
 }
 
 #[cfg(test)]
@@ -808,61 +1044,181 @@ mod tests {
         }
 
         let mut rdr = csv::Reader::from_path(csvfile)?; //Reader::from_reader(io::stdin());
+        let mut failures = Vec::new();
         let mut line_number = 2; //start on line 2 because header row
         for result in rdr.records() {
             // The iterator yields Result<StringRecord, Error>, so we check the error here.
             let record = result?;
+            if let Err(e) = do_csv_toggle(&record) {
+                failures.push(format!("line {}: {}", line_number, e));
+            }
+            line_number += 1;
+        }
 
-            let diacritic = match record[1].trim() {
-                //"none" => HGK_NO_DIACRITICS,
-                "rough" => HGK_ROUGH,
-                "smooth" => HGK_SMOOTH,
-                "acute" => HGK_ACUTE,
-                "grave" => HGK_GRAVE,
-                "circumflex" => HGK_CIRCUMFLEX,
-                "macron" => HGK_MACRON,
-                "breve" => HGK_BREVE,
-                "iotasub" => HGK_IOTA_SUBSCRIPT,
-                "diaeresis" => HGK_DIAERESIS,
-                "underdot" => HGK_UNDERDOT,
-                _ => panic!("Invalid diacritic on line: {}.", line_number)
-            };
+        if !failures.is_empty() {
+            Err(format!("{} failures:\n{}", failures.len(), failures.join("\n")))?
+        }
+        Ok(())
+    }
 
-            let only_on = match record[2].trim() {
-                "onlyon" => true,
-                "toggleoff" => false,
-                _ => panic!("Invalid toggle off on line: {}.", line_number)
-            };
+    fn do_csv_toggle(record:&csv::StringRecord) -> Result<(), String> {
+        let diacritic = csv_diacritic(record[1].trim()).ok_or(format!("invalid diacritic {:?}", &record[1]))?;
 
-            let mode = match record[3].trim() {
-                "CombiningOnly" => HgkUnicodeMode::CombiningOnly,
-                "PrecomposedPUA" => HgkUnicodeMode::PrecomposedPUA,
-                "Precomposed" => HgkUnicodeMode::Precomposed,
-                _ => panic!("Invalid unicode mode on line: {}.", line_number)
-            };
+        let only_on = match record[2].trim() {
+            "onlyon" => true,
+            "toggleoff" => false,
+            t => return Err(format!("invalid toggle off {:?}", t))
+        };
 
-            let is_equal = match record[5].trim() {
-                "equal" => true,
-                "notequal" => false,
-                _ => panic!("Invalid equal on line: {}.", line_number)
-            };
+        let mode = csv_mode(record[3].trim()).ok_or(format!("invalid unicode mode {:?}", &record[3]))?;
 
-            if is_equal {
-                assert_eq!(hgk_toggle_diacritic_str(&hex_to_string(&record[0]), diacritic, only_on, mode), hex_to_string(&record[4]), "Error on line: {}.", line_number);
-            }
-            else {
-                assert_ne!(hgk_toggle_diacritic_str(&hex_to_string(&record[0]), diacritic, only_on, mode), hex_to_string(&record[4]), "Error on line: {}.", line_number);
+        let is_equal = match record[5].trim() {
+            "equal" => true,
+            "notequal" => false,
+            e => return Err(format!("invalid equal {:?}", e))
+        };
+
+        let input = try_hex_to_string(&record[0]).ok_or(format!("invalid hex {:?}", &record[0]))?;
+        let expected = try_hex_to_string(&record[4]).ok_or(format!("invalid hex {:?}", &record[4]))?;
+        let got = hgk_toggle_diacritic_str(&input, diacritic, only_on, mode);
+        if (got == expected) != is_equal {
+            return Err(format!("{} {:?} {:?}: expected {}{}, got {}", &record[0], &record[1], mode,
+                if is_equal { "" } else { "not " }, &record[4], hgk_to_hex(&got)));
+        }
+        Ok(())
+    }
+
+    fn csv_diacritic(name:&str) -> Option<Diacritics> {
+        match name {
+            "rough" => Some(Diacritics::ROUGH),
+            "smooth" => Some(Diacritics::SMOOTH),
+            "acute" => Some(Diacritics::ACUTE),
+            "grave" => Some(Diacritics::GRAVE),
+            "circumflex" => Some(Diacritics::CIRCUMFLEX),
+            "macron" => Some(Diacritics::MACRON),
+            "breve" => Some(Diacritics::BREVE),
+            "iotasub" => Some(Diacritics::IOTA_SUBSCRIPT),
+            "diaeresis" => Some(Diacritics::DIAERESIS),
+            "underdot" => Some(Diacritics::UNDERDOT),
+            _ => None
+        }
+    }
+
+    //a diacritic name, a named set, or several joined with |
+    fn csv_diacritics(names:&str) -> Option<Diacritics> {
+        names.split('|').map(|name| match name.trim() {
+            "" | "none" => Some(Diacritics::NONE),
+            "all" => Some(Diacritics::ALL),
+            "accents" => Some(Diacritics::ACUTE | Diacritics::GRAVE | Diacritics::CIRCUMFLEX),
+            "breathings" => Some(Diacritics::ROUGH | Diacritics::SMOOTH),
+            "length" => Some(Diacritics::MACRON | Diacritics::BREVE),
+            name => csv_diacritic(name)
+        }).collect::<Option<Diacritics>>()
+    }
+
+    fn csv_mode(name:&str) -> Option<HgkUnicodeMode> {
+        match name {
+            "CombiningOnly" => Some(HgkUnicodeMode::CombiningOnly),
+            "CombiningNFD" => Some(HgkUnicodeMode::CombiningNFD),
+            "PrecomposedPUA" => Some(HgkUnicodeMode::PrecomposedPUA),
+            "Precomposed" => Some(HgkUnicodeMode::Precomposed),
+            _ => None
+        }
+    }
+
+    //a string given as text, hex or both, in which case they must agree
+    fn csv_text(text:&str, hex:&str) -> Result<String, String> {
+        if hex.trim().is_empty() {
+            return Ok(String::from(text));
+        }
+        let from_hex = try_hex_to_string(hex).ok_or(format!("invalid hex {:?}", hex))?;
+        //the text may be in any normalization form
+        if !text.is_empty() && text.nfd().ne(from_hex.nfd()) {
+            return Err(format!("text {:?} does not match hex {}", text, hex));
+        }
+        Ok(from_hex)
+    }
+
+    /*
+    Runs the operations in a csv with the columns:
+    op,input,input_hex,other,diacritics,mode,expected,expected_hex,comment
+
+    op is convert, compare, strip, has_diacritics or transliterate.  Strings may be given 
+    as text, utf16 hex or both.  Every failure is returned with its line number.
+    */
+    fn run_csv_ops<R: std::io::Read>(mut rdr:csv::Reader<R>) -> Vec<String> {
+        let mut failures = Vec::new();
+        for (i, result) in rdr.records().enumerate() {
+            let line_number = i + 2; //start on line 2 because header row
+            let record = match result {
+                Ok(r) if r.len() >= 8 => r,
+                Ok(_) => { failures.push(format!("line {}: too few columns", line_number)); continue; },
+                Err(e) => { failures.push(format!("line {}: {}", line_number, e)); continue; }
+            };
+            if let Err(e) = run_csv_op(&record) {
+                failures.push(format!("line {}: {} {:?}: {}", line_number, &record[0], &record[1], e));
             }
-            line_number += 1;
         }
+        failures
+    }
 
-        Ok(())
+    fn run_csv_op(record:&csv::StringRecord) -> Result<(), String> {
+        let input = csv_text(&record[1], &record[2])?;
+        let other = &record[3];
+        let diacritics = csv_diacritics(&record[4]).ok_or(format!("invalid diacritics {:?}", &record[4]))?;
+        let expected = csv_text(&record[6], &record[7])?;
+
+        let got = match record[0].trim() {
+            "convert" => hgk_convert(&input, csv_mode(&record[5]).ok_or(format!("invalid mode {:?}", &record[5]))?),
+            "compare" => hgk_compare(&input, other, diacritics).to_string(),
+            "strip" => hgk_strip_diacritics(&input, diacritics),
+            "has_diacritics" => hgk_has_diacritics(&input, diacritics).to_string(),
+            "transliterate" => match input.chars().next() {
+                Some(c) => hgk_transliterate(c as usize).to_string(),
+                None => return Err(String::from("no input"))
+            },
+            op => return Err(format!("unknown op {:?}", op))
+        };
+
+        if got == expected {
+            Ok(())
+        }
+        else {
+            Err(format!("expected {:?} ({}), got {:?} ({})", expected, hgk_to_hex(&expected), got, hgk_to_hex(&got)))
+        }
+    }
+
+    #[test]
+    fn csv_ops() {
+        let csvfile = "gkops.csv";
+        assert!(Path::new(csvfile).is_file(), "CSV ops file does not exist");
+        let failures = run_csv_ops(csv::Reader::from_path(csvfile).unwrap());
+        assert!(failures.is_empty(), "{} failures:\n{}", failures.len(), failures.join("\n"));
+    }
+
+    #[test]
+    fn csv_ops_reports_all_failures() {
+        let data = "op,input,input_hex,other,diacritics,mode,expected,expected_hex,comment\n\
+            strip,ἄ,,,acute,,α,,\n\
+            strip,ἄ,,,acute,,ἀ,,\n\
+            compare,α,,β,none,,-1,,\n\
+            convert,ά,03B1,,,Precomposed,ά,,\n\
+            frobnicate,α,,,,,α,,\n";
+        let failures = run_csv_ops(csv::Reader::from_reader(data.as_bytes()));
+        assert_eq!(failures.len(), 3);
+        assert!(failures[0].starts_with("line 2: strip"));
+        assert!(failures[1].starts_with("line 5: convert"));
+        assert!(failures[2].starts_with("line 6: frobnicate"));
     }
 
     //make string from utf16 hex codepoints
     fn hex_to_string(s:&str) -> String {
+        try_hex_to_string(s).unwrap()
+    }
+
+    fn try_hex_to_string(s:&str) -> Option<String> {
         //https://stackoverflow.com/questions/3408706/hexadecimal-string-to-byte-array-in-c
-        let b = hex::decode(s.replace(" ", "")).unwrap();
+        let b = hex::decode(s.replace(' ', "")).ok()?;
 
         let res: Vec<u16> = b
         .chunks_exact(2)
@@ -870,7 +1226,7 @@ mod tests {
         .map(|a| u16::from_be_bytes([a[0], a[1]]))
         .collect();
 
-        String::from_utf16( res.as_slice() ).unwrap()
+        String::from_utf16( res.as_slice() ).ok()
     }
 
     #[test]
@@ -894,6 +1250,8 @@ mod tests {
         assert_eq!( hgk_compare("β", "αβ", 0), 1);
       
         assert_eq!( hgk_compare("ἄ", "α", 0xFFFFFFFF), 0);
+        assert_eq!( hgk_compare("λόγος καί", "λόγος ἔργον", 0), 1);
+        assert_eq!( hgk_compare("a1", "a2", 0), -1);
 
         assert_eq!( hgk_compare_sqlite("α", "β"), Ordering::Less );
         assert_eq!( hgk_compare_sqlite("β", "α"), Ordering::Greater );
@@ -906,11 +1264,24 @@ mod tests {
         assert_eq!(v, vec!["αα ωωω", "ααα", "ααβ,ωωω", "\u{EB07}αβα", "βββ"]);
     }
 
+    #[test]
+    fn compare_past_non_greek() {
+        //hgk_compare used to stop at the first pair of equal non-greek characters and return 0,
+        //so all of these compared equal
+        assert_eq!(hgk_compare("λόγος καί", "λόγος ἔργον", 0), 1);
+        assert_eq!(hgk_compare("a1", "a2", 0), -1);
+        assert_eq!(hgk_compare("1α", "1β", 0), -1);
+        assert_eq!(hgk_compare_sqlite("ἄνθρωπος ἀγαθός", "ἄνθρωπος κακός"), Ordering::Less);
+        //equal strings are still equal
+        assert_eq!(hgk_compare("λόγος καί", "λόγος καί", 0), 0);
+        assert_eq!(hgk_compare_sqlite("a1", "a1"), Ordering::Equal);
+    }
+
     #[test]
     fn csv_tests() {
         match do_csv_test() {
             Ok(()) => (),
-            Err(error) => panic!("Error: {:?}", error)
+            Err(error) => panic!("Error: {}", error)
         };
     }
 
@@ -931,7 +1302,7 @@ mod tests {
         //println!("test pua: {}", z4);
 
         //let str = "ἄλφά";
-        //let str2 = str.nfd().chars().iter().filter(|x| !unicode_normalization::char::is_combining_mark(x))
+        //let str2 = str.nfd().chars().iter().filter(|x| !is_combining_mark(x))
 
         assert_eq!(compose('A','\u{30a}'), Some('Å'));
 
@@ -1002,22 +1373,22 @@ mod tests {
     fn iterator_tests() {
         let s = "α\u{0304}\u{0313}\u{0301}βα\u{0313}\u{0301}";//"\u{EB07}βἄ";
         let g = s.gkletters().collect::<Vec<HGKLetter>>();
-        let b: &[_] = &[HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_MACRON | HGK_SMOOTH},HGKLetter{letter:'β', diacritics:0},HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_SMOOTH} ];
+        let b: &[_] = &[HGKLetter::new('α', Diacritics::ACUTE | Diacritics::MACRON | Diacritics::SMOOTH),HGKLetter::new('β', Diacritics::NONE),HGKLetter::new('α', Diacritics::ACUTE | Diacritics::SMOOTH) ];
         assert_eq!(g, b);
 
         let s = "α\u{0304}\u{0313}\u{0301}βἄ";//"\u{EB07}βἄ";
         let g = s.gkletters().collect::<Vec<HGKLetter>>();
-        let b: &[_] = &[HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_MACRON | HGK_SMOOTH},HGKLetter{letter:'β', diacritics:0},HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_SMOOTH} ];
+        let b: &[_] = &[HGKLetter::new('α', Diacritics::ACUTE | Diacritics::MACRON | Diacritics::SMOOTH),HGKLetter::new('β', Diacritics::NONE),HGKLetter::new('α', Diacritics::ACUTE | Diacritics::SMOOTH) ];
         assert_eq!(g, b);
 
         let s = "\u{EB07}βἄ";//"ᾱ̓́βἄ";//
         let g = s.gkletters().collect::<Vec<HGKLetter>>();
-        let b: &[_] = &[HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_MACRON | HGK_SMOOTH},HGKLetter{letter:'β', diacritics:0},HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_SMOOTH} ];
+        let b: &[_] = &[HGKLetter::new('α', Diacritics::ACUTE | Diacritics::MACRON | Diacritics::SMOOTH),HGKLetter::new('β', Diacritics::NONE),HGKLetter::new('α', Diacritics::ACUTE | Diacritics::SMOOTH) ];
         assert_eq!(g, b);
 
         let s = "\u{1F04}βἄ";//"ᾱ̓́βἄ";//
         let g = s.gkletters().collect::<Vec<HGKLetter>>();
-        let b: &[_] = &[HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_SMOOTH},HGKLetter{letter:'β', diacritics:0},HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_SMOOTH} ];
+        let b: &[_] = &[HGKLetter::new('α', Diacritics::ACUTE | Diacritics::SMOOTH),HGKLetter::new('β', Diacritics::NONE),HGKLetter::new('α', Diacritics::ACUTE | Diacritics::SMOOTH) ];
         assert_eq!(g, b);
 
         
@@ -1035,44 +1406,44 @@ mod tests {
 
 
         let mut aaa = "\u{1FE1}".gkletters();
-        assert_eq!(aaa.next().unwrap(), HGKLetter{letter:'υ', diacritics:HGK_MACRON});
+        assert_eq!(aaa.next().unwrap(), HGKLetter::new('υ', Diacritics::MACRON));
 
         let mut aaa = "υ\u{0304}".gkletters();
-        assert_eq!(aaa.next_back().unwrap(), HGKLetter{letter:'υ', diacritics:HGK_MACRON});
+        assert_eq!(aaa.next_back().unwrap(), HGKLetter::new('υ', Diacritics::MACRON));
 
         let mut aaa = "λυ\u{0304}ε".gkletters();
-        assert_eq!(aaa.next_back().unwrap(), HGKLetter{letter:'ε', diacritics:0});
-        assert_eq!(aaa.next_back().unwrap(), HGKLetter{letter:'υ', diacritics:HGK_MACRON});
-        assert_eq!(aaa.next_back().unwrap(), HGKLetter{letter:'λ', diacritics:0});
+        assert_eq!(aaa.next_back().unwrap(), HGKLetter::new('ε', Diacritics::NONE));
+        assert_eq!(aaa.next_back().unwrap(), HGKLetter::new('υ', Diacritics::MACRON));
+        assert_eq!(aaa.next_back().unwrap(), HGKLetter::new('λ', Diacritics::NONE));
         assert_eq!(aaa.next_back(), None);
 
         let s = "αβγ";
         let g = s.gkletters().collect::<Vec<HGKLetter>>();
-        let b: &[_] = &[HGKLetter{letter:'α', diacritics:0},HGKLetter{letter:'β', diacritics:0},HGKLetter{letter:'γ', diacritics:0} ];
+        let b: &[_] = &[HGKLetter::new('α', Diacritics::NONE),HGKLetter::new('β', Diacritics::NONE),HGKLetter::new('γ', Diacritics::NONE) ];
         assert_eq!(g, b);
 
         let s = "ᾱ̓́";
         let g = s.gkletters().collect::<Vec<HGKLetter>>();
-        let b: &[_] = &[HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_MACRON | HGK_SMOOTH} ];
+        let b: &[_] = &[HGKLetter::new('α', Diacritics::ACUTE | Diacritics::MACRON | Diacritics::SMOOTH) ];
         assert_eq!(g, b);
 
         let s = "\u{EB07}";
         let g = s.gkletters().collect::<Vec<HGKLetter>>();
-        let b: &[_] = &[HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_MACRON | HGK_SMOOTH} ];
+        let b: &[_] = &[HGKLetter::new('α', Diacritics::ACUTE | Diacritics::MACRON | Diacritics::SMOOTH) ];
         assert_eq!(g, b);
 
         let s = "\u{EB07}βἄ";
         let g = s.gkletters().collect::<Vec<HGKLetter>>();
-        let b: &[_] = &[HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_MACRON | HGK_SMOOTH},HGKLetter{letter:'β', diacritics:0},HGKLetter{letter:'α', diacritics:HGK_ACUTE | HGK_SMOOTH} ];
+        let b: &[_] = &[HGKLetter::new('α', Diacritics::ACUTE | Diacritics::MACRON | Diacritics::SMOOTH),HGKLetter::new('β', Diacritics::NONE),HGKLetter::new('α', Diacritics::ACUTE | Diacritics::SMOOTH) ];
         assert_eq!(g, b);
 
         let s = "\u{EB07}βᾱ";
-        let xxx = s.gkletters().map(|a| HGKLetter{letter:a.letter, diacritics:0} ).collect::<Vec<HGKLetter>>();
-        let b: &[_] = &[HGKLetter{letter:'α', diacritics:0},HGKLetter{letter:'β', diacritics:0},HGKLetter{letter:'α', diacritics:0} ];
+        let xxx = s.gkletters().map(|a| HGKLetter::new(a.letter, Diacritics::NONE) ).collect::<Vec<HGKLetter>>();
+        let b: &[_] = &[HGKLetter::new('α', Diacritics::NONE),HGKLetter::new('β', Diacritics::NONE),HGKLetter::new('α', Diacritics::NONE) ];
         assert_eq!(xxx, b);
 
         let s = "\u{EB07}βἄ";
-        let xxx = s.gkletters().map(|a| HGKLetter{letter:a.letter, diacritics:0}.to_string(HgkUnicodeMode::PrecomposedPUA)).collect::<String>();
+        let xxx = s.gkletters().map(|a| HGKLetter::new(a.letter, Diacritics::NONE).to_string(HgkUnicodeMode::PrecomposedPUA)).collect::<String>();
         assert_eq!(xxx, "αβα");
     }
 
@@ -1167,6 +1538,42 @@ mod tests {
         }
     }
     
+    //every letter which toggle_diacritic can produce, starting from the bare vowels and rho
+    pub(crate) fn reachable_letters() -> Vec<HGKLetter> {
+        let mut res:Vec<HGKLetter> = Vec::new();
+        for base in "αεηιουωρΑΕΗΙΟΥΩΡ".chars() {
+            let mut queue = vec![Diacritics::NONE];
+            let mut seen = vec![Diacritics::NONE];
+            while let Some(d) = queue.pop() {
+                res.push(HGKLetter::new(base, d));
+                for t in Diacritics::ALL {
+                    let mut l = HGKLetter::new(base, d);
+                    l.toggle_diacritic(t, false);
+                    if !seen.contains(&l.diacritics) {
+                        seen.push(l.diacritics);
+                        queue.push(l.diacritics);
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn pua_round_trip() {
+        let mut failures = Vec::new();
+        for l in reachable_letters() {
+            for mode in [HgkUnicodeMode::Precomposed, HgkUnicodeMode::CombiningOnly, HgkUnicodeMode::PrecomposedPUA] {
+                let s = l.to_string(mode);
+                let back = s.gkletters().collect::<Vec<HGKLetter>>();
+                if back.len() != 1 || back[0] != l {
+                    failures.push(format!("{:?} {:?} {:X?}", l, back, s.chars().map(|c| c as u32).collect::<Vec<u32>>()));
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
     #[test]
     fn mytest() {
         //println!("{:?}", env::current_dir().unwrap());
@@ -1201,77 +1608,180 @@ mod tests {
         let _aa = HGKLetter::from_str("\u{EAF0}");
 
         let a2 = HGKLetter::from_str("\u{03B1}\u{0301}");
-        assert_eq!(a2.diacritics & HGK_ACUTE, HGK_ACUTE);
+        assert_eq!(a2.diacritics & Diacritics::ACUTE, Diacritics::ACUTE);
         assert_eq!(a2.letter, '\u{03B1}');
         let a3 = HGKLetter::from_str("\u{03AC}");
-        assert_eq!(a3.diacritics & HGK_ACUTE, HGK_ACUTE);
+        assert_eq!(a3.diacritics & Diacritics::ACUTE, Diacritics::ACUTE);
         assert_eq!(a3.letter, '\u{03B1}');
 
-        let mut s: HGKLetter = HGKLetter { letter: 'α', diacritics: HGK_ACUTE | HGK_GRAVE };
-        assert_eq!(s.diacritics & HGK_ACUTE, HGK_ACUTE);
-        assert_ne!(s.diacritics & HGK_CIRCUMFLEX, HGK_CIRCUMFLEX);
+        let mut s: HGKLetter = HGKLetter::new('α', Diacritics::ACUTE | Diacritics::GRAVE);
+        assert_eq!(s.diacritics & Diacritics::ACUTE, Diacritics::ACUTE);
+        assert_ne!(s.diacritics & Diacritics::CIRCUMFLEX, Diacritics::CIRCUMFLEX);
 
-        s.toggle_diacritic(HGK_CIRCUMFLEX, true);
-        assert_eq!(s.diacritics & HGK_CIRCUMFLEX, HGK_CIRCUMFLEX);
+        s.toggle_diacritic(Diacritics::CIRCUMFLEX, true);
+        assert_eq!(s.diacritics & Diacritics::CIRCUMFLEX, Diacritics::CIRCUMFLEX);
         //don't toggle off, if on_only is set
-        s.toggle_diacritic(HGK_CIRCUMFLEX, true);
-        assert_eq!(s.diacritics & HGK_CIRCUMFLEX, HGK_CIRCUMFLEX);
+        s.toggle_diacritic(Diacritics::CIRCUMFLEX, true);
+        assert_eq!(s.diacritics & Diacritics::CIRCUMFLEX, Diacritics::CIRCUMFLEX);
         //turn off
-        s.toggle_diacritic(HGK_CIRCUMFLEX, false);
-        assert_ne!(s.diacritics & HGK_CIRCUMFLEX, HGK_CIRCUMFLEX);
+        s.toggle_diacritic(Diacritics::CIRCUMFLEX, false);
+        assert_ne!(s.diacritics & Diacritics::CIRCUMFLEX, Diacritics::CIRCUMFLEX);
+
+        let mut s = HGKLetter::new('α', Diacritics::MACRON | Diacritics::ACUTE | Diacritics::SMOOTH);
+        assert_eq!(s.try_toggle_diacritic(Diacritics::CIRCUMFLEX, false), HgkToggleResult::Applied(Diacritics::ACUTE | Diacritics::MACRON));
+        assert_eq!(s.diacritics, Diacritics::CIRCUMFLEX | Diacritics::SMOOTH);
+        assert_eq!(s.try_toggle_diacritic(Diacritics::CIRCUMFLEX, false), HgkToggleResult::Removed(Diacritics::NONE));
+        assert_eq!(s.try_toggle_diacritic(Diacritics::IOTA_SUBSCRIPT, false), HgkToggleResult::Applied(Diacritics::NONE));
+        assert_eq!(s.try_toggle_diacritic(Diacritics::ROUGH, true), HgkToggleResult::Applied(Diacritics::SMOOTH));
+        assert_eq!(s.diacritics, Diacritics::ROUGH | Diacritics::IOTA_SUBSCRIPT);
+
+        let mut s = HGKLetter::new('ι', Diacritics::NONE);
+        assert_eq!(s.try_toggle_diacritic(Diacritics::IOTA_SUBSCRIPT, false), HgkToggleResult::Rejected(HgkRejectReason::IotaSubscriptNotOnAlphaEtaOmega));
+        assert_eq!(HgkRejectReason::IotaSubscriptNotOnAlphaEtaOmega.description(), "iota subscript only on α/η/ω");
+        assert_eq!(s.try_toggle_diacritic(Diacritics::from(0xFFFF), false), HgkToggleResult::Rejected(HgkRejectReason::UnknownDiacritic));
+        assert_eq!(s.diacritics, Diacritics::NONE);
+
+        //the old u32 constants still work
+        s.toggle_diacritic(HGK_DIAERESIS, false);
+        assert_eq!(s.diacritics, Diacritics::DIAERESIS);
 
  
         let mut a1 = HGKLetter::from_str("υ");
         assert_eq!(a1.letter, 'υ');
-        assert_eq!(a1.diacritics, HGK_NO_DIACRITICS);
-        a1.toggle_diacritic(HGK_MACRON, false);
+        assert_eq!(a1.diacritics, Diacritics::NONE);
+        a1.toggle_diacritic(Diacritics::MACRON, false);
         assert_eq!(a1.letter, 'υ');
-        assert_eq!(a1.diacritics, HGK_MACRON);
+        assert_eq!(a1.diacritics, Diacritics::MACRON);
         assert_eq!(get_pua_index(a1.letter, a1.diacritics), -1);
         assert_eq!(a1.to_string(HgkUnicodeMode::PrecomposedPUA), "\u{1FE1}");
 
-        assert_eq!(hgk_toggle_diacritic_str("υ", HGK_MACRON, false, HgkUnicodeMode::PrecomposedPUA), 
+        assert_eq!(hgk_toggle_diacritic_str("υ", Diacritics::MACRON, false, HgkUnicodeMode::PrecomposedPUA), 
             "\u{1FE1}");
 
-        assert_eq!(hgk_toggle_diacritic_str("α", HGK_UNDERDOT, false, HgkUnicodeMode::PrecomposedPUA), 
+        assert_eq!(hgk_toggle_diacritic_str("α", Diacritics::UNDERDOT, false, HgkUnicodeMode::PrecomposedPUA), 
             "\u{03B1}\u{0323}");
 
-        assert_eq!(hgk_toggle_diacritic_str("ἀ", HGK_MACRON, false, HgkUnicodeMode::PrecomposedPUA), 
+        assert_eq!(hgk_toggle_diacritic_str("ἀ", Diacritics::MACRON, false, HgkUnicodeMode::PrecomposedPUA), 
             "\u{EB04}");
-        assert_eq!(hgk_toggle_diacritic_str("ἄ", HGK_MACRON, false, HgkUnicodeMode::PrecomposedPUA), 
+        assert_eq!(hgk_toggle_diacritic_str("ἄ", Diacritics::MACRON, false, HgkUnicodeMode::PrecomposedPUA), 
             "\u{EB07}");
-        assert_eq!(hgk_toggle_diacritic_str("ὺ", HGK_BREVE, false, HgkUnicodeMode::PrecomposedPUA), 
+        assert_eq!(hgk_toggle_diacritic_str("ὺ", Diacritics::BREVE, false, HgkUnicodeMode::PrecomposedPUA), 
             "\u{EB83}");
-        assert_eq!(hgk_toggle_diacritic_str("α", HGK_ACUTE, false, HgkUnicodeMode::PrecomposedPUA), 
+        assert_eq!(hgk_toggle_diacritic_str("α", Diacritics::ACUTE, false, HgkUnicodeMode::PrecomposedPUA), 
             "\u{03AC}");
 
-        assert_eq!(hgk_toggle_diacritic_str("α", HGK_ACUTE, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("α", Diacritics::ACUTE, false, HgkUnicodeMode::Precomposed), 
             "\u{03AC}");//ά");
-        assert_eq!(hgk_toggle_diacritic_str("ὰ", HGK_ACUTE, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("ὰ", Diacritics::ACUTE, false, HgkUnicodeMode::Precomposed), 
             "\u{03AC}");//ά");
-        assert_eq!(hgk_toggle_diacritic_str("α", HGK_ACUTE, false, HgkUnicodeMode::CombiningOnly), 
+        assert_eq!(hgk_toggle_diacritic_str("α", Diacritics::ACUTE, false, HgkUnicodeMode::CombiningOnly), 
             "\u{03B1}\u{0301}");
-        assert_eq!(hgk_toggle_diacritic_str("α", HGK_CIRCUMFLEX, false, HgkUnicodeMode::CombiningOnly), 
+        assert_eq!(hgk_toggle_diacritic_str("α", Diacritics::CIRCUMFLEX, false, HgkUnicodeMode::CombiningOnly), 
             "\u{03B1}\u{0342}");
-        assert_eq!(hgk_toggle_diacritic_str("α", HGK_CIRCUMFLEX, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("α", Diacritics::CIRCUMFLEX, false, HgkUnicodeMode::Precomposed), 
             "\u{1FB6}");
-        assert_eq!(hgk_toggle_diacritic_str("ε", HGK_CIRCUMFLEX, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("ε", Diacritics::CIRCUMFLEX, false, HgkUnicodeMode::Precomposed), 
             "ε");
-        assert_eq!(hgk_toggle_diacritic_str("ω", HGK_CIRCUMFLEX, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("ω", Diacritics::CIRCUMFLEX, false, HgkUnicodeMode::Precomposed), 
             "ῶ");
-        assert_eq!(hgk_toggle_diacritic_str("ρ", HGK_ROUGH, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("ρ", Diacritics::ROUGH, false, HgkUnicodeMode::Precomposed), 
             "ῥ");
-        assert_eq!(hgk_toggle_diacritic_str("Ρ", HGK_ROUGH, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("Ρ", Diacritics::ROUGH, false, HgkUnicodeMode::Precomposed), 
             "Ῥ");
-        assert_eq!(hgk_toggle_diacritic_str("ρ", HGK_SMOOTH, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("ρ", Diacritics::SMOOTH, false, HgkUnicodeMode::Precomposed), 
             "ῤ");
-        assert_eq!(hgk_toggle_diacritic_str("Ρ", HGK_SMOOTH, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("Ρ", Diacritics::SMOOTH, false, HgkUnicodeMode::Precomposed), 
             "Ρ\u{0313}"); //there is no precomposed capital rho with smooth breathing
-        assert_eq!(hgk_toggle_diacritic_str("Ρ\u{0313}", HGK_SMOOTH, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("Ρ\u{0313}", Diacritics::SMOOTH, false, HgkUnicodeMode::Precomposed), 
             "Ρ");
-        assert_eq!(hgk_toggle_diacritic_str("Ρ\u{0313}", HGK_ROUGH, false, HgkUnicodeMode::Precomposed), 
+        assert_eq!(hgk_toggle_diacritic_str("Ρ\u{0313}", Diacritics::ROUGH, false, HgkUnicodeMode::Precomposed), 
             "Ῥ");
-        assert_eq!(hgk_toggle_diacritic_str("Ρ\u{0313}", HGK_ROUGH, false, HgkUnicodeMode::CombiningOnly), 
+        assert_eq!(hgk_toggle_diacritic_str("Ρ\u{0313}", Diacritics::ROUGH, false, HgkUnicodeMode::CombiningOnly), 
             "Ρ\u{0314}");
     }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "toggle_diacritic takes one diacritic")]
+    fn toggle_combined_diacritics() {
+        HGKLetter::new('α', Diacritics::NONE).toggle_diacritic(Diacritics::ALL, false);
+    }
+
+    #[test]
+    fn unknown_marks() {
+        let l = "α\u{0333}\u{0313}".gkletters().collect::<Vec<HGKLetter>>();
+        assert_eq!(l.len(), 1);
+        assert_eq!(l[0].diacritics, Diacritics::SMOOTH);
+        assert_eq!(l[0].marks.as_slice(), &['\u{0333}']);
+        assert_eq!(hgk_convert("α\u{0333}\u{0313}", HgkUnicodeMode::Precomposed), "ἀ\u{0333}");
+        assert_eq!(hgk_convert("ά\u{0311}", HgkUnicodeMode::CombiningOnly), "α\u{0301}\u{0311}");
+        assert_eq!(hgk_convert("\u{EB07}\u{0311}", HgkUnicodeMode::PrecomposedPUA), "\u{EB07}\u{0311}");
+        assert_eq!(HGKLetter::from_str("ε\u{0307}\u{0301}").marks.as_slice(), &['\u{0307}']);
+
+        //more marks than fit start a new letter
+        let many = "α\u{0311}\u{0311}\u{0311}\u{0311}\u{0311}\u{0301}";
+        assert_eq!(many.gkletters().count(), 2);
+
+        for s in ["α\u{0311}", "\u{0311}α\u{0307}β", "\u{0301}α", many, "ἄν\u{0361}θρω\u{0323}\u{0333}πος", "\u{1F71}\u{0311} a\u{0300}\u{0331}"] {
+            let forward = s.gkletters().collect::<Vec<HGKLetter>>();
+            let mut back = s.gkletters().rev().collect::<Vec<HGKLetter>>();
+            back.reverse();
+            assert_eq!(forward, back, "{:X?}", s);
+            for mode in [HgkUnicodeMode::Precomposed, HgkUnicodeMode::CombiningOnly, HgkUnicodeMode::PrecomposedPUA] {
+                let converted = hgk_convert(s, mode);
+                let expected = if mode == HgkUnicodeMode::PrecomposedPUA { hgk_convert(s, HgkUnicodeMode::Precomposed) } else { String::from(s) };
+                assert_eq!(hgk_convert(&converted, HgkUnicodeMode::Precomposed).nfd().collect::<String>(), expected.nfd().collect::<String>(), "{:X?} {:?}", s, mode);
+            }
+        }
+
+        //walking back over a long run of marks finds the same letters as walking forward
+        let long = format!("β{}γ", "\u{0311}\u{0301}\u{0333}".repeat(2000));
+        for s in [long.as_str(), &long[2..], "α\u{0311}\u{0311}\u{0311}\u{0311}\u{0311}\u{0311}\u{0311}\u{0311}\u{0311}\u{0311}\u{0311}"] {
+            let forward = s.gkletters().collect::<Vec<HGKLetter>>();
+            let mut back = s.gkletters().rev().collect::<Vec<HGKLetter>>();
+            back.reverse();
+            assert!(forward.len() > 2);
+            assert_eq!(forward, back);
+        }
+
+        //marks stay where they were written among the diacritics, so conversion is canonically equivalent
+        assert_eq!(hgk_convert("α\u{0311}\u{0301}", HgkUnicodeMode::CombiningOnly), "α\u{0311}\u{0301}");
+        assert_eq!(hgk_convert("α\u{0311}\u{0301}", HgkUnicodeMode::Precomposed), "α\u{0311}\u{0301}");
+        assert_ne!("α\u{0311}\u{0301}".parse::<HGKLetter>(), "α\u{0301}\u{0311}".parse::<HGKLetter>());
+        let overflow = format!("α{}\u{0301}", "\u{0311}".repeat(9));
+        for s in ["α\u{0311}\u{0301}", "ἀ\u{0333}\u{0301}", "α\u{0313}\u{0311}\u{0301}\u{0345}", "\u{1F71}\u{0311}\u{0345}", "ω\u{0342}\u{0311}\u{0345}\u{0323}", overflow.as_str()] {
+            for mode in [HgkUnicodeMode::CombiningOnly, HgkUnicodeMode::CombiningNFD, HgkUnicodeMode::Precomposed] {
+                assert_eq!(hgk_convert(s, mode).nfd().collect::<String>(), s.nfd().collect::<String>(), "{:X?} {:?}", s, mode);
+            }
+            let pua = hgk_convert(s, HgkUnicodeMode::PrecomposedPUA);
+            assert_eq!(hgk_convert(&pua, HgkUnicodeMode::CombiningOnly).nfd().collect::<String>(), s.nfd().collect::<String>(), "{:X?}", s);
+        }
+        assert_eq!(hgk_convert("α\u{0313}\u{0311}\u{0301}", HgkUnicodeMode::PrecomposedPUA), "ἀ\u{0311}\u{0301}");
+    }
+
+    #[test]
+    fn letter_ord_and_parse() {
+        assert_eq!("ᾄ".parse::<HGKLetter>(), Ok(HGKLetter::new('α', Diacritics::SMOOTH | Diacritics::ACUTE | Diacritics::IOTA_SUBSCRIPT)));
+        assert_eq!("α\u{0313}\u{0301}".parse::<HGKLetter>(), "ἄ".parse::<HGKLetter>());
+        assert_eq!("\u{EB07}".parse::<HGKLetter>(), Ok(HGKLetter::new('α', Diacritics::MACRON | Diacritics::SMOOTH | Diacritics::ACUTE)));
+        assert_eq!("".parse::<HGKLetter>(), Err(HgkLetterParseError::Empty));
+        assert_eq!("αβ".parse::<HGKLetter>(), Err(HgkLetterParseError::MultipleLetters));
+        assert_eq!("ά ".parse::<HGKLetter>(), Err(HgkLetterParseError::MultipleLetters));
+        assert_eq!("\u{0301}".parse::<HGKLetter>(), Err(HgkLetterParseError::LeadingMark));
+
+        let mut letters = "ωβἄΑaάςσα".gkletters().collect::<Vec<HGKLetter>>();
+        letters.sort();
+        assert_eq!(letters.iter().map(|l| l.to_string(HgkUnicodeMode::Precomposed)).collect::<String>(), "aΑαάἄβςσω");
+        //agrees with hgk_compare where it doesn't tie
+        for a in &letters {
+            for b in &letters {
+                let c = hgk_compare(&a.to_string(HgkUnicodeMode::Precomposed), &b.to_string(HgkUnicodeMode::Precomposed), 0);
+                assert!(c == 0 || c == a.cmp(b) as i32, "{:?} {:?}", a, b);
+            }
+        }
+
+        let set = letters.iter().copied().collect::<std::collections::HashSet<HGKLetter>>();
+        assert_eq!(set.len(), 9);
+        assert!(set.contains(&HGKLetter::new('ς', Diacritics::NONE)));
+    }
 }
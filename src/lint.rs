@@ -0,0 +1,196 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum HgkLintSeverity {
+    Warning,
+    Error
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HgkLintKind {
+    IllegalDiacritic(Diacritics, HgkRejectReason), //e.g. circumflex on ε/ο
+    ClashingDiacritics(Diacritics),                //e.g. acute and grave on one letter
+    BreathingNotInitial,
+    MultipleAccents,                               //may be legitimate before an enclitic
+    GraveNotOnUltima,
+    DiaeresisWithoutDiphthong
+}
+
+//an unambiguous fix: remove and then add diacritics on the letter
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct HgkLintFix {
+    pub remove: Diacritics,
+    pub add: Diacritics
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HgkLintDiagnostic {
+    pub range: Range<usize>,
+    pub severity: HgkLintSeverity,
+    pub kind: HgkLintKind,
+    pub fix: Option<HgkLintFix>
+}
+
+const ACCENTS:Diacritics = Diacritics::ACUTE.union(Diacritics::GRAVE).union(Diacritics::CIRCUMFLEX);
+const BREATHINGS:Diacritics = Diacritics::ROUGH.union(Diacritics::SMOOTH);
+
+//a letter of the greek alphabet, including the archaic letters and numeral signs
+pub(crate) fn hgk_is_greek_letter(c:char) -> bool {
+    (0x0370..=0x03FF).contains(&(c as u32)) && GREEK_BASIC[c as usize - 0x0370].2 != 0
+}
+
+fn to_lower(c:char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+//would a and b form a diphthong if b had no diaeresis
+pub(crate) fn hgk_is_diphthong(a:char, b:char) -> bool {
+    matches!((to_lower(a), to_lower(b)), ('α' | 'ε' | 'ο' | 'υ', 'ι') | ('α' | 'ε' | 'η' | 'ο' | 'ω', 'υ'))
+}
+
+fn is_rho(c:char) -> bool {
+    c == 'ρ' || c == 'Ρ'
+}
+
+/// Check polytonic text for impossible or suspicious diacritic combinations.
+pub fn hgk_lint(text:&str) -> Vec<HgkLintDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut word:Vec<(Range<usize>, HGKLetter)> = Vec::new();
+
+    for (range, letter) in text.gkletter_spans() {
+        lint_letter(&range, &letter, &mut diagnostics);
+
+        if hgk_is_greek_letter(letter.letter) {
+            word.push((range, letter));
+        }
+        else {
+            lint_word(&word, &mut diagnostics);
+            word.clear();
+        }
+    }
+    lint_word(&word, &mut diagnostics);
+
+    diagnostics.sort_by_key(|d| d.range.start);
+    diagnostics
+}
+
+fn lint_letter(range:&Range<usize>, letter:&HGKLetter, diagnostics:&mut Vec<HgkLintDiagnostic>) {
+    for d in letter.diacritics {
+        if let Some(reason) = letter.reject_reason(d) {
+            diagnostics.push(HgkLintDiagnostic { range: range.clone(), severity: HgkLintSeverity::Error, kind: HgkLintKind::IllegalDiacritic(d, reason), fix: None });
+        }
+    }
+
+    for d in letter.diacritics {
+        let clashing = letter.diacritics & hgk_clashing_diacritics(d);
+        if !clashing.is_empty() {
+            diagnostics.push(HgkLintDiagnostic { range: range.clone(), severity: HgkLintSeverity::Error, kind: HgkLintKind::ClashingDiacritics(d | clashing), fix: None });
+            break;
+        }
+    }
+}
+
+fn lint_word(word:&[(Range<usize>, HGKLetter)], diagnostics:&mut Vec<HgkLintDiagnostic>) {
+    if word.is_empty() {
+        return;
+    }
+
+    let ultima = word.iter().rposition(|(_, l)| l.letter.is_greek_vowel());
+    let mut accents = 0;
+
+    for (i, (range, l)) in word.iter().enumerate() {
+        if l.diacritics.intersects(BREATHINGS) {
+            let allowed = if is_rho(l.letter) {
+                //initial rho, or the old double rho convention ῤῥ
+                i == 0 || is_rho(word[i - 1].1.letter) || word.get(i + 1).is_some_and(|n| is_rho(n.1.letter))
+            }
+            else {
+                //breathing goes on the second vowel of an initial diphthong
+                i == 0 || (i == 1 && hgk_is_diphthong(word[0].1.letter, l.letter) && !l.diacritics.contains(Diacritics::DIAERESIS) && !word[0].1.diacritics.intersects(BREATHINGS))
+            };
+            if !allowed {
+                diagnostics.push(HgkLintDiagnostic { range: range.clone(), severity: HgkLintSeverity::Error, kind: HgkLintKind::BreathingNotInitial, fix: None });
+            }
+        }
+
+        if l.diacritics.intersects(ACCENTS) {
+            accents += 1;
+        }
+
+        //an accent before the ultima cannot be grave, so it must be acute
+        if l.diacritics.contains(Diacritics::GRAVE) && l.letter.is_greek_vowel() && Some(i) != ultima {
+            let fix = HgkLintFix { remove: Diacritics::GRAVE, add: Diacritics::ACUTE };
+            diagnostics.push(HgkLintDiagnostic { range: range.clone(), severity: HgkLintSeverity::Error, kind: HgkLintKind::GraveNotOnUltima, fix: Some(fix) });
+        }
+
+        if l.diacritics.contains(Diacritics::DIAERESIS) && (i == 0 || !hgk_is_diphthong(word[i - 1].1.letter, l.letter)) {
+            let fix = HgkLintFix { remove: Diacritics::DIAERESIS, add: Diacritics::NONE };
+            diagnostics.push(HgkLintDiagnostic { range: range.clone(), severity: HgkLintSeverity::Warning, kind: HgkLintKind::DiaeresisWithoutDiphthong, fix: Some(fix) });
+        }
+    }
+
+    if accents > 1 {
+        let range = word[0].0.start..word[word.len() - 1].0.end;
+        diagnostics.push(HgkLintDiagnostic { range, severity: HgkLintSeverity::Warning, kind: HgkLintKind::MultipleAccents, fix: None });
+    }
+}
+
+/// Apply the unambiguous fixes found by hgk_lint.  Fixed letters are rendered in unicode_mode,
+/// everything else is copied unchanged.
+pub fn hgk_lint_fix<'a>(text:&str, unicode_mode:impl Into<HgkOutputMode<'a>>) -> String {
+    let unicode_mode = unicode_mode.into();
+    let diagnostics = hgk_lint(text);
+    let mut res = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for (range, letter) in text.gkletter_spans() {
+        let mut fixed = letter.diacritics;
+        for fix in diagnostics.iter().filter(|d| d.range == range).filter_map(|d| d.fix) {
+            fixed = (fixed - fix.remove) | fix.add;
+        }
+        if fixed != letter.diacritics {
+            res.push_str(&text[last..range.start]);
+            res.push_str(&HGKLetter { letter: letter.letter, diacritics: fixed, marks: letter.marks }.to_string(unicode_mode));
+            last = range.end;
+        }
+    }
+    res.push_str(&text[last..]);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(s:&str) -> Vec<HgkLintKind> {
+        hgk_lint(s).into_iter().map(|d| d.kind).collect()
+    }
+
+    #[test]
+    fn lint_rules() {
+        assert_eq!(kinds("ἄνθρωπος λόγου καὶ οἱ αὐτοὶ ῥήτορες ἔῤῥωσο ἀΐδιος"), vec![]);
+
+        assert_eq!(kinds("λὀγος"), vec![HgkLintKind::BreathingNotInitial]);
+        assert_eq!(kinds("ἀλόγῶ"), vec![HgkLintKind::MultipleAccents]);
+        assert_eq!(kinds("λε\u{0342}γε"), vec![HgkLintKind::IllegalDiacritic(Diacritics::CIRCUMFLEX, HgkRejectReason::CircumflexNotOnLongVowel)]);
+        assert_eq!(kinds("α\u{0300}\u{0301}"), vec![HgkLintKind::ClashingDiacritics(Diacritics::ACUTE | Diacritics::GRAVE)]);
+        assert_eq!(kinds("λὸγος"), vec![HgkLintKind::GraveNotOnUltima]);
+        assert_eq!(kinds("πόλϊς"), vec![HgkLintKind::DiaeresisWithoutDiphthong]);
+
+        let d = hgk_lint("ὁ λὸγος");
+        assert_eq!(d.len(), 1);
+        assert_eq!(d[0].range, 6..9);
+        assert_eq!(d[0].severity, HgkLintSeverity::Error);
+        assert_eq!(&"ὁ λὸγος"[d[0].range.clone()], "ὸ");
+    }
+
+    #[test]
+    fn lint_fixes() {
+        assert_eq!(hgk_lint_fix("ὁ λὸγος, πόλϊς.", HgkUnicodeMode::Precomposed), "ὁ λόγος, πόλις.");
+        assert_eq!(hgk_lint_fix("λὀγος", HgkUnicodeMode::Precomposed), "λὀγος");
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::*;
+
+const ACCENTS:Diacritics = Diacritics::ACUTE.union(Diacritics::GRAVE).union(Diacritics::CIRCUMFLEX);
+
+fn lower(c:char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+//calls f on each run of greek letters, and pushes everything else to res unchanged
+fn map_words(text:&str, mut f:impl FnMut(&[HGKLetter], &[HGKLetter], &mut String)) -> String {
+    let (ranges, letters):(Vec<Range<usize>>, Vec<HGKLetter>) = text.gkletter_spans().unzip();
+    let mut res = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < letters.len() {
+        if hgk_is_greek_letter(letters[i].letter) {
+            let end = letters[i..].iter().position(|l| !hgk_is_greek_letter(l.letter)).map_or(letters.len(), |p| i + p);
+            let mut word = String::new();
+            f(&letters[i..end], &letters[end..], &mut word);
+            res.extend(word.nfc());
+            i = end;
+        }
+        else {
+            res.push_str(&text[ranges[i].clone()]);
+            i += 1;
+        }
+    }
+    res
+}
+
+fn syllables(word:&[HGKLetter]) -> usize {
+    let mut count = 0;
+    for (i, l) in word.iter().enumerate() {
+        let c = lower(l.letter);
+        if !c.is_greek_vowel() {
+            continue;
+        }
+        let joins = i > 0 && hgk_is_diphthong(lower(word[i - 1].letter), c) && !l.diacritics.contains(Diacritics::DIAERESIS)
+            && !word[i - 1].diacritics.intersects(ACCENTS | Diacritics::IOTA_SUBSCRIPT);
+        if !joins {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn letters_to_string(letters:&[HGKLetter]) -> String {
+    letters.iter().map(|l| l.to_string(HgkUnicodeMode::Precomposed)).collect::<String>()
+}
+
+fn monotonic_word(word:&[HGKLetter], res:&mut String) {
+    let accent = word.iter().map(|l| l.diacritics & ACCENTS).find(|d| !d.is_empty());
+    let mut letters = word.iter().map(|l| {
+        let mut diacritics = l.diacritics & Diacritics::DIAERESIS;
+        if l.diacritics.intersects(ACCENTS) {
+            diacritics |= Diacritics::ACUTE;
+        }
+        HGKLetter { letter: l.letter, diacritics, marks: l.marks }
+    }).collect::<Vec<HGKLetter>>();
+
+    if syllables(word) == 1 {
+        let form = letters_to_string(&letters).nfc().collect::<String>().to_lowercase();
+        let keep = MONOTONIC_ACCENTED_MONOSYLLABLES.iter().any(|m| m.0 == form && (m.1 || accent != Some(Diacritics::GRAVE)));
+        if !keep {
+            for l in letters.iter_mut() {
+                l.diacritics.remove(Diacritics::ACUTE);
+            }
+        }
+    }
+    res.push_str(&letters_to_string(&letters));
+}
+
+/// Convert polytonic text to monotonic: breathings, iota subscripts and length marks are
+/// dropped, any accent becomes tonos and diaeresis is kept.  Monosyllables lose their accent
+/// except ή, and πού and πώς written with acute or circumflex.
+pub fn hgk_to_monotonic(text:&str) -> String {
+    map_words(text, |word, _, res| monotonic_word(word, res))
+}
+
+/// Best-effort monotonic to polytonic conversion from a list of polytonic words.
+///
+/// Words are looked up by their monotonic form; when several words share one, the first in
+/// the list wins.  Words not in the list are left unchanged.
+#[derive(Debug, Default)]
+pub struct HgkPolytonicLexicon {
+    forms: BTreeMap<String, String>
+}
+
+impl HgkPolytonicLexicon {
+    pub fn new<'a>(words:impl IntoIterator<Item = &'a str>) -> HgkPolytonicLexicon {
+        let mut lexicon = HgkPolytonicLexicon::default();
+        for w in words {
+            lexicon.insert(w);
+        }
+        lexicon
+    }
+
+    pub fn insert(&mut self, word:&str) {
+        let word = word.trim();
+        if !word.is_empty() {
+            self.forms.entry(hgk_to_monotonic(word).to_lowercase()).or_insert_with(|| word.nfc().collect::<String>());
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.forms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.forms.is_empty()
+    }
+
+    /// The polytonic form of a single monotonic word, as it is in the list.
+    pub fn lookup(&self, word:&str) -> Option<&str> {
+        self.forms.get(&word.nfc().collect::<String>().to_lowercase()).map(|s| s.as_str())
+    }
+
+    /// Convert monotonic text.  Case follows the input, all-caps words are left unaccented,
+    /// and a final acute becomes grave when another word follows.
+    pub fn to_polytonic(&self, text:&str) -> String {
+        map_words(text, |word, rest, res| {
+            let form = letters_to_string(word);
+            let all_caps = word.len() > 1 && word.iter().all(|l| l.letter.is_uppercase());
+            let Some(polytonic) = self.lookup(&form).filter(|_| !all_caps) else {
+                res.push_str(&form);
+                return;
+            };
+
+            let mut letters = polytonic.gkletters().collect::<Vec<HGKLetter>>();
+            if let (Some(first), Some(w)) = (letters.first_mut(), word.first()) {
+                if w.letter.is_uppercase() {
+                    first.letter = first.letter.to_uppercase().next().unwrap_or(first.letter);
+                }
+            }
+
+            //oxytones take a grave before another word
+            let followed = rest.iter().find(|l| !l.letter.is_whitespace()).is_some_and(|l| hgk_is_greek_letter(l.letter))
+                && rest.first().is_some_and(|l| l.letter.is_whitespace());
+            if let Some(last) = letters.iter_mut().rev().find(|l| lower(l.letter).is_greek_vowel()) {
+                if followed && last.diacritics.contains(Diacritics::ACUTE) {
+                    last.diacritics.remove(Diacritics::ACUTE);
+                    last.diacritics.insert(Diacritics::GRAVE);
+                }
+            }
+            res.push_str(&letters_to_string(&letters));
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monotonic() {
+        assert_eq!(hgk_to_monotonic("Ἐν ἀρχῇ ἦν ὁ λόγος, καὶ ὁ λόγος ἦν πρὸς τὸν θεόν"), "Εν αρχή ην ο λόγος, και ο λόγος ην προς τον θεόν");
+        assert_eq!(hgk_to_monotonic("ἄνθρωπος οἶκος ἀΐδιος ᾠδῇ"), "άνθρωπος οίκος αΐδιος ωδή");
+        assert_eq!(hgk_to_monotonic("ἢ ποῦ πῶς ποὺ"), "ή πού πώς που");
+        assert_eq!(hgk_to_monotonic("Ἅιδης \u{EB07}μα"), "Άιδης άμα");
+        assert_eq!(hgk_to_monotonic("άνθρωπος"), "\u{03AC}νθρωπος");
+        //other text is not normalized
+        assert_eq!(hgk_to_monotonic("e\u{0301} λόγος"), "e\u{0301} λόγος");
+    }
+
+    #[test]
+    fn polytonic() {
+        let lexicon = HgkPolytonicLexicon::new(["ἐν", "ἀρχῇ", "ἦν", "ὁ", "ἡ", "λόγος", "καί", "θεός", "ποῦ", "πού"]);
+        assert_eq!(lexicon.len(), 9);
+        assert_eq!(lexicon.lookup("λογος"), None);
+        assert_eq!(lexicon.lookup("ΛΌΓΟΣ"), Some("λόγος"));
+
+        let text = "Εν αρχή ην ο λόγος και ο θεός. ΘΕΟΣ πού; άγνωστος";
+        let polytonic = lexicon.to_polytonic(text);
+        assert_eq!(polytonic, "Ἐν ἀρχῇ ἦν ὁ λόγος καὶ ὁ θεός. ΘΕΟΣ ποῦ; άγνωστος");
+        assert_eq!(hgk_to_monotonic(&polytonic), text);
+        assert_eq!(lexicon.to_polytonic("e\u{0301} ο λόγος"), "e\u{0301} ὁ λόγος");
+    }
+}
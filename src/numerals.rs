@@ -0,0 +1,118 @@
+use alloc::string::String;
+
+use crate::*;
+
+fn render(n:u32, digits:&[[char; 9]; 3]) -> Option<String> {
+    if !(1..=9999).contains(&n) {
+        return None;
+    }
+    let mut s = String::new();
+    if n >= 1000 {
+        s.push(HGK_LOWER_KERAIA);
+        s.push(digits[0][(n / 1000) as usize - 1]);
+    }
+    for (place, power) in [(2, 100), (1, 10), (0, 1)] {
+        let digit = (n / power) % 10;
+        if digit > 0 {
+            s.push(digits[place][digit as usize - 1]);
+        }
+    }
+    s.push(HGK_KERAIA);
+    Some(s)
+}
+
+/// The alphabetic numeral for 1–9999, e.g. 2023 -> ͵βκγʹ.  Thousands are marked
+/// with the lower keraia and the number ends with a keraia.
+pub fn to_greek_numeral(n:u32) -> Option<String> {
+    render(n, &GREEK_NUMERALS_LOWER)
+}
+
+pub fn to_greek_numeral_upper(n:u32) -> Option<String> {
+    render(n, &GREEK_NUMERALS_UPPER)
+}
+
+fn numeral_value(c:char) -> Option<u32> {
+    for (place, power) in [(0, 1), (1, 10), (2, 100)] {
+        for digits in [&GREEK_NUMERALS_LOWER, &GREEK_NUMERALS_UPPER] {
+            if let Some(i) = digits[place].iter().position(|d| *d == c) {
+                return Some((i as u32 + 1) * power);
+            }
+        }
+    }
+    GREEK_NUMERAL_VARIANTS.iter().find(|v| v.0 == c).map(|v| v.1)
+}
+
+/// Parse an alphabetic numeral in either case.  The final keraia is optional and may be
+/// U+0374 or its normalized form U+02B9.  Digits must be in descending order, each place
+/// used at most once, so e.g. "βκ" is not a numeral.
+pub fn parse_greek_numeral(s:&str) -> Option<u32> {
+    let s = s.trim();
+    let s = s.strip_suffix(HGK_KERAIA).or_else(|| s.strip_suffix('\u{02B9}')).unwrap_or(s);
+
+    let mut chars = s.chars();
+    let mut total = 0;
+    let mut last_power = 10000;
+    while let Some(c) = chars.next() {
+        let value = if c == HGK_LOWER_KERAIA {
+            match chars.next().and_then(numeral_value) {
+                Some(v) if v < 10 => v * 1000,
+                _ => return None
+            }
+        }
+        else {
+            numeral_value(c)?
+        };
+
+        let power = [1000, 100, 10, 1].into_iter().find(|p| value >= *p)?;
+        if power >= last_power {
+            return None;
+        }
+        last_power = power;
+        total += value;
+    }
+
+    if total == 0 { None } else { Some(total) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greek_numerals() {
+        assert_eq!(to_greek_numeral(2023), Some(String::from("\u{0375}βκγ\u{0374}")));
+        assert_eq!(to_greek_numeral(6), Some(String::from("\u{03DB}\u{0374}")));
+        assert_eq!(to_greek_numeral(999), Some(String::from("\u{03E1}\u{03DF}θ\u{0374}")));
+        assert_eq!(to_greek_numeral(1000), Some(String::from("\u{0375}α\u{0374}")));
+        assert_eq!(to_greek_numeral_upper(666), Some(String::from("ΧΞ\u{03DA}\u{0374}")));
+        assert_eq!(to_greek_numeral(0), None);
+        assert_eq!(to_greek_numeral(10000), None);
+
+        assert_eq!(parse_greek_numeral("͵βκγʹ"), Some(2023));
+        assert_eq!(parse_greek_numeral("ΡΚΔ"), Some(124));
+        assert_eq!(parse_greek_numeral("\u{03DD}\u{0374}"), Some(6));
+        assert_eq!(parse_greek_numeral("ςʹ"), Some(6));
+        assert_eq!(parse_greek_numeral("χξςʹ"), Some(666));
+        assert_eq!(parse_greek_numeral("βκ"), None);
+        assert_eq!(parse_greek_numeral("κβα"), None);
+        assert_eq!(parse_greek_numeral("λόγος"), None);
+        assert_eq!(parse_greek_numeral("\u{0375}"), None);
+        assert_eq!(parse_greek_numeral(""), None);
+
+        for n in 1..=9999 {
+            assert_eq!(parse_greek_numeral(&to_greek_numeral(n).unwrap()), Some(n));
+            assert_eq!(parse_greek_numeral(&to_greek_numeral_upper(n).unwrap()), Some(n));
+        }
+
+        //numeral letters sort with the alphabet
+        assert_eq!(hgk_compare("\u{03DB}", "\u{03DD}", 0), 0);
+        assert_eq!(hgk_compare("\u{03E1}", "ω", 0), 1);
+        assert_eq!(hgk_compare("κγ\u{0374}", "κγ", 0), 1);
+
+        //the keraiai weigh as punctuation, not as a letter after ω
+        assert_eq!(hgk_compare("\u{0374}", "α", 0), -1);
+        assert_eq!(hgk_compare("\u{0375}α", "ω", 0), -1);
+        assert_eq!(hgk_compare("κγ\u{0374}", "κγ\u{037E}", 0), 0);
+        assert_eq!(hgk_compare("\u{0375}β\u{0374}", "\u{0374}β\u{0375}", 0), 0);
+    }
+}
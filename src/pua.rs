@@ -0,0 +1,189 @@
+use alloc::string::String;
+
+use crate::*;
+
+/// A private use area encoding of precomposed letters which unicode lacks,
+/// e.g. alpha with macron, smooth breathing and acute.
+///
+/// decode is indexed by codepoint - first, like GREEK_PUA; '\u{0000}' entries are unassigned.
+/// encode is indexed by get_pua_index(), like GREEK_LOWER_PUA; '\u{0000}' entries are unassigned.
+/// Indices 0–47 are lowercase α, ι, υ; 48–95 capital Α, Ι, Υ; 96–113 α and Α with
+/// macron and iota subscript.  A shorter table leaves the rest unassigned.
+///
+/// The tables may be borrowed from data loaded at run time.  Only PUA_SCHEME_DEFAULT is built
+/// in; text typed with another font's PUA, e.g. Cardo's or SIL's, needs a scheme made from
+/// that font's chart.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PuaScheme<'a> {
+    pub name: &'a str,
+    pub first: u32,
+    pub decode: &'a [(char, u32)],
+    pub encode: &'a [char]
+}
+
+/// The scheme at U+EAF0–U+EB8A used by PrecomposedPUA and gkletters().
+///
+/// It only has lowercase α, ι and υ with macron or breve.  Capital Α, Ι and Υ with macron
+/// or breve, and macron with iota subscript, have no codepoint in it, so PrecomposedPUA
+/// writes them as precomposed unicode with combining marks.  A scheme for a font which
+/// has them can be made with PuaScheme::new().
+pub static PUA_SCHEME_DEFAULT: PuaScheme<'static> = PuaScheme::new("default", 0xEAF0, GREEK_PUA, GREEK_LOWER_PUA);
+
+impl<'a> PuaScheme<'a> {
+    pub const fn new(name:&'a str, first:u32, decode:&'a [(char, u32)], encode:&'a [char]) -> PuaScheme<'a> {
+        PuaScheme { name, first, decode, encode }
+    }
+
+    pub fn contains(&self, ch:char) -> bool {
+        (ch as u32) >= self.first && ((ch as u32 - self.first) as usize) < self.decode.len()
+    }
+
+    /// The letter and diacritics of a PUA codepoint, or None if it is not assigned in this scheme.
+    pub fn decode(&self, ch:char) -> Option<(char, Diacritics)> {
+        if !self.contains(ch) {
+            return None;
+        }
+        let entry = self.decode[(ch as u32 - self.first) as usize];
+        if entry.0 == '\u{0000}' || entry.0 == NOT_ACCENTABLE_CHAR {
+            None
+        }
+        else {
+            Some((entry.0, Diacritics::from_bits_truncate(entry.1)))
+        }
+    }
+
+    /// The PUA codepoint for letter and diacritics.  Iota subscript and underdot are
+    /// ignored since they are added as combining marks.
+    pub fn encode(&self, letter:char, diacritics:Diacritics) -> Option<char> {
+        self.lookup(get_pua_index(letter, diacritics))
+    }
+
+    /// The PUA codepoint for letter and diacritics which includes the iota subscript,
+    /// e.g. alpha with macron, rough breathing and iota subscript.  Underdot is ignored.
+    pub fn encode_iota(&self, letter:char, diacritics:Diacritics) -> Option<char> {
+        self.lookup(get_pua_iota_index(letter, diacritics))
+    }
+
+    fn lookup(&self, idx:i32) -> Option<char> {
+        if idx < 0 || idx as usize >= self.encode.len() || self.encode[idx as usize] == '\u{0000}' {
+            None
+        }
+        else {
+            Some(self.encode[idx as usize])
+        }
+    }
+}
+
+/// Re-encode PUA characters from one scheme to another.  Letters which the target
+/// scheme cannot encode fall back to precomposed unicode with combining marks.
+pub fn hgk_convert_pua(l:&str, from:&PuaScheme, to:&PuaScheme) -> String {
+    l.gkletters_pua(from).map(|a| a.to_string_pua(HgkUnicodeMode::PrecomposedPUA, to)).collect::<String>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_DECODE: &[(char, u32)] = &[
+        /* F000 */ ( '\u{03B1}', HGK_MACRON | HGK_SMOOTH ),
+        /* F001 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+        /* F002 */ ( '\u{03B1}', HGK_MACRON | HGK_SMOOTH | HGK_ACUTE ),
+        /* F003 */ ( '\u{0391}', HGK_MACRON | HGK_SMOOTH ),
+        /* F004 */ ( '\u{03A5}', HGK_BREVE | HGK_ROUGH | HGK_GRAVE ),
+        /* F005 */ ( '\u{03B1}', HGK_MACRON | HGK_IOTA_SUBSCRIPT ),
+        /* F006 */ ( '\u{03B1}', HGK_MACRON | HGK_ROUGH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+        /* F007 */ ( '\u{0391}', HGK_MACRON | HGK_SMOOTH | HGK_IOTA_SUBSCRIPT )
+    ];
+    static TEST_ENCODE: [char; 114] = {
+        let mut a = ['\u{0000}'; 114];
+        a[0] = '\u{F000}';
+        a[1] = '\u{F002}';
+        a[48] = '\u{F003}';
+        a[93] = '\u{F004}';
+        a[96] = '\u{F005}';
+        a[101] = '\u{F006}';
+        a[106] = '\u{F007}';
+        a
+    };
+    static TEST_SCHEME: PuaScheme = PuaScheme::new("test", 0xF000, TEST_DECODE, &TEST_ENCODE);
+
+    #[test]
+    fn pua_schemes() {
+        assert_eq!(PUA_SCHEME_DEFAULT.decode('\u{EB07}'), Some(('α', Diacritics::MACRON | Diacritics::SMOOTH | Diacritics::ACUTE)));
+        assert_eq!(PUA_SCHEME_DEFAULT.decode('\u{EAF1}'), None);
+        assert_eq!(PUA_SCHEME_DEFAULT.encode('α', Diacritics::MACRON | Diacritics::SMOOTH | Diacritics::ACUTE), Some('\u{EB07}'));
+        assert_eq!(TEST_SCHEME.encode('α', Diacritics::MACRON | Diacritics::ROUGH), None);
+
+        let s = "\u{F002}β\u{F001}";
+        let g = s.gkletters_pua(&TEST_SCHEME).collect::<Vec<HGKLetter>>();
+        assert_eq!(g[0], HGKLetter::new('α', Diacritics::MACRON | Diacritics::SMOOTH | Diacritics::ACUTE));
+        assert_eq!(g[2], HGKLetter::new('\u{F001}', Diacritics::NONE));
+        assert_eq!(hgk_convert(s, HgkUnicodeMode::PrecomposedPUA), s);
+
+        assert_eq!(hgk_convert_pua(s, &TEST_SCHEME, &PUA_SCHEME_DEFAULT), "\u{EB07}β\u{F001}");
+        assert_eq!(hgk_convert_pua("\u{EB04}\u{EB05}", &PUA_SCHEME_DEFAULT, &TEST_SCHEME), "\u{F000}\u{1FB1}\u{0314}");
+        assert_eq!(HGKLetter::new('α', Diacritics::MACRON | Diacritics::SMOOTH).to_string_pua(HgkUnicodeMode::PrecomposedPUA, &TEST_SCHEME), "\u{F000}");
+    }
+
+    #[test]
+    fn default_scheme() {
+        //every assigned codepoint is what PrecomposedPUA writes for its letter
+        for c in (0xEAF0..=0xEB8A).filter_map(char::from_u32) {
+            if let Some((letter, diacritics)) = PUA_SCHEME_DEFAULT.decode(c) {
+                let l = HGKLetter::new(letter, diacritics);
+                assert_eq!(l.to_string(HgkUnicodeMode::PrecomposedPUA), c.to_string(), "{:X}", c as u32);
+                assert_eq!(c.to_string().gkletters().collect::<Vec<HGKLetter>>(), vec![l]);
+            }
+        }
+
+        //the default table has no capitals with macron or breve, or iota subscript forms: these are
+        //written with combining marks, on the PUA letter where there is one
+        let pua = |letter, diacritics| HGKLetter::new(letter, diacritics).to_string(HgkUnicodeMode::PrecomposedPUA);
+        assert_eq!(pua('Α', Diacritics::MACRON | Diacritics::SMOOTH), "\u{1FB9}\u{0313}");
+        assert_eq!(pua('Υ', Diacritics::BREVE | Diacritics::ROUGH | Diacritics::GRAVE), "\u{1FE8}\u{0314}\u{0300}");
+        assert_eq!(pua('α', Diacritics::MACRON | Diacritics::IOTA_SUBSCRIPT), "\u{1FB1}\u{0345}");
+        assert_eq!(pua('α', Diacritics::MACRON | Diacritics::SMOOTH | Diacritics::ACUTE | Diacritics::IOTA_SUBSCRIPT), "\u{EB07}\u{0345}");
+        for (letter, diacritics) in [('Α', Diacritics::MACRON | Diacritics::SMOOTH), ('Ι', Diacritics::BREVE | Diacritics::ACUTE), ('α', Diacritics::MACRON | Diacritics::ROUGH | Diacritics::IOTA_SUBSCRIPT)] {
+            assert_eq!(pua(letter, diacritics).gkletters().collect::<Vec<HGKLetter>>(), vec![HGKLetter::new(letter, diacritics)]);
+        }
+    }
+
+    #[test]
+    fn runtime_scheme() {
+        let name = String::from("loaded");
+        let decode = vec![('α', HGK_MACRON | HGK_SMOOTH)];
+        let mut encode = vec!['\u{0000}'; 48];
+        encode[0] = '\u{F100}';
+        let scheme = PuaScheme::new(&name, 0xF100, &decode, &encode);
+
+        let mode = HgkUnicodeMode::PrecomposedPUA.with_pua(&scheme);
+        assert_eq!(hgk_convert("ἀ\u{0304}β\u{EB04}", mode), "\u{F100}β\u{EB04}");
+        assert_eq!(hgk_convert("\u{F100}", HgkUnicodeMode::CombiningOnly.with_pua(&scheme)), "α\u{0304}\u{0313}");
+        assert_eq!(hgk_toggle_diacritic_str("\u{F100}", Diacritics::SMOOTH, false, mode), "\u{1FB1}");
+        assert_eq!(HGKLetter::new('α', Diacritics::MACRON | Diacritics::SMOOTH).to_string(mode), "\u{F100}");
+        assert!(hgk_quick_check("\u{F100}β", mode));
+        assert!(!hgk_quick_check("ἀ\u{0304}", mode));
+        assert_eq!(hgk_convert_pua("\u{F100}", &scheme, &PUA_SCHEME_DEFAULT), "\u{EB04}");
+        assert_eq!("\u{F100}".gkletters_pua(&scheme).next(), Some(HGKLetter::new('α', Diacritics::MACRON | Diacritics::SMOOTH)));
+    }
+
+    #[test]
+    fn pua_capitals_and_iota() {
+        let pua = |letter, diacritics| HGKLetter::new(letter, diacritics).to_string_pua(HgkUnicodeMode::PrecomposedPUA, &TEST_SCHEME);
+        assert_eq!(pua('Α', Diacritics::MACRON | Diacritics::SMOOTH), "\u{F003}");
+        assert_eq!(pua('Υ', Diacritics::BREVE | Diacritics::ROUGH | Diacritics::GRAVE), "\u{F004}");
+        assert_eq!(pua('α', Diacritics::MACRON | Diacritics::IOTA_SUBSCRIPT), "\u{F005}");
+        assert_eq!(pua('α', Diacritics::MACRON | Diacritics::ROUGH | Diacritics::ACUTE | Diacritics::IOTA_SUBSCRIPT | Diacritics::UNDERDOT), "\u{F006}\u{0323}");
+        assert_eq!(pua('Α', Diacritics::MACRON | Diacritics::SMOOTH | Diacritics::IOTA_SUBSCRIPT), "\u{F007}");
+        //no iota form: the non-iota codepoint plus combining iota subscript
+        assert_eq!(pua('α', Diacritics::MACRON | Diacritics::SMOOTH | Diacritics::IOTA_SUBSCRIPT), "\u{F000}\u{0345}");
+        //the default scheme has no capitals
+        assert_eq!(PUA_SCHEME_DEFAULT.encode('Α', Diacritics::MACRON | Diacritics::SMOOTH), None);
+
+        for l in crate::tests::reachable_letters() {
+            let s = l.to_string_pua(HgkUnicodeMode::PrecomposedPUA, &TEST_SCHEME);
+            let back = s.gkletters_pua(&TEST_SCHEME).collect::<Vec<HGKLetter>>();
+            assert_eq!(back, vec![HGKLetter::new(l.letter, l.diacritics)], "{:X?}", s);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use alloc::vec::Vec;
+
+use unicode_normalization::char::{canonical_combining_class, decompose_canonical, is_combining_mark};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::*;
+
+/// What a text is made of, from hgk_normalization_report().  Offsets are byte offsets.
+///
+/// A greek letter may count as both precomposed and combining, e.g. ἀ followed by U+0345.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct HgkNormalizationReport {
+    pub greek_letters: usize,
+    pub precomposed: usize,                     //written with a precomposed character which has diacritics
+    pub combining: usize,                       //followed by combining marks
+    pub pua: usize,                             //written with a private use area character
+    pub oxia: usize,                            //precomposed with oxia, e.g. U+1F71, which NFC turns into tonos
+    pub tonos: usize,                           //precomposed with tonos, e.g. U+03AC
+    pub non_canonical_order: Vec<Range<usize>>, //letters whose combining marks are not in unicode canonical order
+    pub unknown_marks: Vec<(usize, char)>,      //combining marks other than the ten diacritics, kept in HGKLetter::marks
+    pub mixed_script: Vec<(usize, char)>        //non-greek letters inside a word with greek letters, e.g. latin o
+}
+
+impl HgkNormalizationReport {
+    /// True if nothing would change or be lost in conversion.
+    pub fn is_clean(&self) -> bool {
+        self.oxia == 0 && self.non_canonical_order.is_empty() && self.unknown_marks.is_empty() && self.mixed_script.is_empty()
+    }
+}
+
+fn is_canonical_order(span:&str) -> bool {
+    let mut classes = Vec::new();
+    for c in span.chars() {
+        decompose_canonical(c, |d| classes.push(canonical_combining_class(d)));
+    }
+    classes.windows(2).all(|w| w[1] == 0 || w[0] <= w[1])
+}
+
+fn decomposes_with_acute(c:char) -> bool {
+    let mut acute = false;
+    decompose_canonical(c, |d| acute |= d == '\u{0301}');
+    acute
+}
+
+pub fn hgk_normalization_report(text:&str) -> HgkNormalizationReport {
+    hgk_normalization_report_pua(text, &PUA_SCHEME_DEFAULT)
+}
+
+pub fn hgk_normalization_report_pua(text:&str, pua:&PuaScheme) -> HgkNormalizationReport {
+    let mut report = HgkNormalizationReport::default();
+    let mut cursor = GreekLetterCursor::new_with_pua(0, text.len(), pua);
+    let mut word:Vec<(usize, char)> = Vec::new(); //the letters of the current word
+    let mut word_is_greek = false;
+
+    while cursor.cur_cursor() < text.len() {
+        let start = cursor.cur_cursor();
+        let Ok(Some(letter)) = cursor.next_boundary(text, 0) else { break };
+        let span = &text[start..cursor.cur_cursor()];
+        let first = span.chars().next().unwrap_or(letter.letter);
+
+        if !letter.letter.is_alphabetic() {
+            if word_is_greek {
+                report.mixed_script.append(&mut word);
+            }
+            word.clear();
+            word_is_greek = false;
+        }
+
+        for (i, c) in span.char_indices() {
+            if is_combining_mark(c) && !hgk_is_combining(c) {
+                report.unknown_marks.push((start + i, c));
+            }
+        }
+        if is_combining_mark(letter.letter) {
+            continue;
+        }
+        if !hgk_is_greek_letter(letter.letter) {
+            if letter.letter.is_alphabetic() {
+                word.push((start, letter.letter));
+            }
+            continue;
+        }
+
+        word_is_greek = true;
+        report.greek_letters += 1;
+        if pua.decode(first).is_some() {
+            report.pua += 1;
+        }
+        else if first != letter.letter && hgk_is_greek_letter(letter.letter) && decompose_count(first) > 1 {
+            report.precomposed += 1;
+            if decomposes_with_acute(first) {
+                if ('\u{1F00}'..='\u{1FFF}').contains(&first) && first.to_string().nfc().next() != Some(first) {
+                    report.oxia += 1;
+                }
+                else if ('\u{0370}'..='\u{03FF}').contains(&first) {
+                    report.tonos += 1;
+                }
+            }
+        }
+        if span.chars().count() > 1 {
+            report.combining += 1;
+        }
+        if !is_canonical_order(span) {
+            report.non_canonical_order.push(start..cursor.cur_cursor());
+        }
+    }
+    if word_is_greek {
+        report.mixed_script.append(&mut word);
+    }
+    report
+}
+
+fn decompose_count(c:char) -> usize {
+    let mut n = 0;
+    decompose_canonical(c, |_| n += 1);
+    n
+}
+
+/// True if hgk_convert(text, mode) would return text unchanged.
+pub fn hgk_quick_check<'a>(text:&str, mode:impl Into<HgkOutputMode<'a>>) -> bool {
+    let mode = mode.into();
+    text.gkletter_spans_pua(mode.pua).all(|(range, letter)| letter.to_string(mode) == text[range])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalization_report() {
+        let text = "ἄνθρωπος \u{1F71}\u{03AC}\u{03B1}\u{0301} α\u{0345}\u{0301} \u{EB07} λόγoς ε\u{0307}";
+        let report = hgk_normalization_report(text);
+        assert_eq!(report.greek_letters, 18);
+        assert_eq!(report.precomposed, 4);
+        assert_eq!(report.combining, 3);
+        assert_eq!(report.pua, 1);
+        assert_eq!(report.oxia, 1);
+        assert_eq!(report.tonos, 2);
+        let bad = text.find("α\u{0345}").unwrap();
+        assert_eq!(report.non_canonical_order, vec![bad..bad + 6]);
+        assert_eq!(report.unknown_marks, vec![(text.len() - 2, '\u{0307}')]);
+        assert_eq!(report.mixed_script, vec![(text.find('o').unwrap(), 'o')]);
+        assert!(!report.is_clean());
+
+        let report = hgk_normalization_report("ἄνθρωπος, ABC καὶ ἡμέρα.");
+        assert_eq!(report.mixed_script, vec![]);
+        assert!(report.is_clean());
+
+        let report = hgk_normalization_report("λόγος\u{0333}");
+        assert_eq!(report.unknown_marks, vec![(10, '\u{0333}')]);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn quick_check() {
+        assert!(hgk_quick_check("ἄνθρωπος ab", HgkUnicodeMode::Precomposed));
+        assert!(!hgk_quick_check("\u{1F71}", HgkUnicodeMode::Precomposed));
+        assert!(!hgk_quick_check("ἄνθρωπος", HgkUnicodeMode::CombiningOnly));
+        assert!(hgk_quick_check(&hgk_convert("ἄνθρωπος", HgkUnicodeMode::CombiningOnly), HgkUnicodeMode::CombiningOnly));
+    }
+}
@@ -0,0 +1,176 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HgkRomanization {
+    AlaLc,      //ALA-LC ancient greek: no accents, iota subscript as i
+    Sbl,        //SBL academic style: no accents, iota subscript as ogonek
+    Scientific  //accents, length, diaeresis, iota subscript and underdot are kept as combining marks
+}
+
+fn is_upper(c:char) -> bool {
+    c.is_uppercase()
+}
+
+fn lower(c:char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Romanize greek text.  Anything which is not a greek letter is copied unchanged.
+///
+/// The scientific scheme is lossless for standard orthography: smooth breathing is implied
+/// on an initial vowel without h, and γ is always g.
+pub fn hgk_romanize(text:&str, scheme:HgkRomanization) -> String {
+    let (ranges, letters):(Vec<Range<usize>>, Vec<HGKLetter>) = text.gkletter_spans().unzip();
+    let mut res = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < letters.len() {
+        if hgk_is_greek_letter(letters[i].letter) {
+            let end = letters[i..].iter().position(|l| !hgk_is_greek_letter(l.letter)).map_or(letters.len(), |p| i + p);
+            //only the romanized letters are composed, other text is left as it was
+            let mut word = String::new();
+            romanize_word(&letters[i..end], scheme, &mut word);
+            res.extend(word.nfc());
+            i = end;
+        }
+        else {
+            res.push_str(&text[ranges[i].clone()]);
+            i += 1;
+        }
+    }
+    res
+}
+
+fn romanize_word(word:&[HGKLetter], scheme:HgkRomanization, res:&mut String) {
+    let lower_word = word.iter().map(|l| lower(l.letter)).collect::<Vec<char>>();
+    let all_caps = word.len() > 1 && word.iter().all(|l| is_upper(l.letter) || !l.letter.is_alphabetic());
+
+    //rough breathing is written before an initial diphthong, not on its second vowel
+    let mut h_before = vec![false; word.len()];
+    for (i, l) in word.iter().enumerate() {
+        if l.diacritics.contains(Diacritics::ROUGH) && lower_word[i] != 'ρ' {
+            if i == 1 && hgk_is_diphthong(lower_word[0], lower_word[1]) && !l.diacritics.contains(Diacritics::DIAERESIS) {
+                h_before[0] = true;
+            }
+            else {
+                h_before[i] = true;
+            }
+        }
+    }
+
+    for (i, l) in word.iter().enumerate() {
+        let c = lower_word[i];
+        let next = lower_word.get(i + 1).copied();
+        let next_has_diaeresis = word.get(i + 1).is_some_and(|n| n.diacritics.contains(Diacritics::DIAERESIS));
+
+        let mut piece = String::new();
+        if h_before[i] {
+            piece.push('h');
+        }
+
+        match ROMAN_LETTERS.iter().find(|r| r.0 == c) {
+            Some(_) if c == 'γ' && scheme != HgkRomanization::Scientific && matches!(next, Some('γ' | 'κ' | 'ξ' | 'χ')) => piece.push('n'),
+            Some(_) if c == 'υ' && !l.diacritics.contains(Diacritics::DIAERESIS)
+                && ((i > 0 && hgk_is_diphthong(lower_word[i - 1], c)) || (next == Some('ι') && !next_has_diaeresis)) => piece.push('u'),
+            Some(r) => piece.push_str(r.1),
+            None => piece.push(c)
+        }
+        if c == 'ρ' && l.diacritics.contains(Diacritics::ROUGH) {
+            piece.push('h');
+        }
+        if c.is_greek_vowel() {
+            push_vowel_marks(l, scheme, &mut piece);
+        }
+
+        if all_caps {
+            piece = piece.to_uppercase();
+        }
+        else if is_upper(l.letter) {
+            let mut chars = piece.chars();
+            if let Some(first) = chars.next() {
+                piece = first.to_uppercase().chain(chars).collect::<String>();
+            }
+        }
+        res.push_str(&piece);
+    }
+}
+
+fn push_vowel_marks(l:&HGKLetter, scheme:HgkRomanization, piece:&mut String) {
+    let d = l.diacritics;
+    let scientific = scheme == HgkRomanization::Scientific;
+
+    //a circumflex already shows the vowel is long
+    if (l.letter.is_long() || (scientific && d.contains(Diacritics::MACRON))) && !(scientific && d.contains(Diacritics::CIRCUMFLEX)) {
+        piece.push('\u{0304}');
+    }
+    if scientific && d.contains(Diacritics::BREVE) {
+        piece.push('\u{0306}');
+    }
+    if d.contains(Diacritics::DIAERESIS) {
+        piece.push('\u{0308}');
+    }
+    if scientific {
+        if d.contains(Diacritics::ACUTE) {
+            piece.push('\u{0301}');
+        }
+        if d.contains(Diacritics::GRAVE) {
+            piece.push('\u{0300}');
+        }
+        if d.contains(Diacritics::CIRCUMFLEX) {
+            piece.push('\u{0302}');
+        }
+        if d.contains(Diacritics::UNDERDOT) {
+            piece.push('\u{0323}');
+        }
+    }
+    if d.contains(Diacritics::IOTA_SUBSCRIPT) {
+        if scheme == HgkRomanization::AlaLc {
+            piece.push('i');
+        }
+        else {
+            piece.push('\u{0328}');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn romanize_ala_lc() {
+        let r = |s| hgk_romanize(s, HgkRomanization::AlaLc);
+        assert_eq!(r("ἄνθρωπος"), "anthrōpos");
+        assert_eq!(r("ῥήτωρ"), "rhētōr");
+        assert_eq!(r("οἱ αὐτοὶ"), "hoi autoi");
+        assert_eq!(r("υἱός"), "huios");
+        assert_eq!(r("ἄγγελος, σφίγξ"), "angelos, sphinx");
+        assert_eq!(r("λύκος ἀϋτή"), "lykos aÿtē");
+        assert_eq!(r("ᾠδή"), "ōidē");
+        assert_eq!(r("Ὅμηρος Οἱ ΘΕΟΣ"), "Homēros Hoi THEOS");
+    }
+
+    #[test]
+    fn romanize_sbl_and_scientific() {
+        assert_eq!(hgk_romanize("ᾠδή τῇ", HgkRomanization::Sbl), "ǭdē tę̄");
+        assert_eq!(hgk_romanize("ἄγγελος", HgkRomanization::Sbl), "angelos");
+
+        let r = |s| hgk_romanize(s, HgkRomanization::Scientific);
+        assert_eq!(r("ἄνθρωπος τῆς"), "ánthrōpos tês");
+        assert_eq!(r("ῥᾳδίως"), "rhądíōs");
+        assert_eq!(r("ἄγγελος"), "ággelos");
+        assert_eq!(r("\u{EB07}ῦ"), "\u{0101}\u{0301}\u{00FB}");
+        assert_eq!(r("λῦε"), "l\u{0177}e");
+        assert_eq!(r("Ἅιδης"), "Háidēs");
+
+        //other text is not normalized
+        assert_eq!(r("e\u{0301} λόγος"), "e\u{0301} lógos");
+        assert_eq!(hgk_romanize("A\u{030A}ngstro\u{0308}m", HgkRomanization::AlaLc), "A\u{030A}ngstro\u{0308}m");
+    }
+}
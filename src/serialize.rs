@@ -0,0 +1,178 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::de::{Deserializer, Error};
+use unicode_normalization::char::is_combining_mark;
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//Diacritics are a list of lower case names in canonical order, e.g. ["smooth", "acute"]
+impl Serialize for Diacritics {
+    fn serialize<S:Serializer>(&self, serializer:S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().filter_map(|d| d.name()).map(|n| n.to_ascii_lowercase()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Diacritics {
+    fn deserialize<D:Deserializer<'de>>(deserializer:D) -> Result<Diacritics, D::Error> {
+        Vec::<String>::deserialize(deserializer)?.iter().map(|n| {
+            Diacritics::from_name(n).ok_or_else(|| D::Error::custom(format!("unknown diacritic {:?}", n)))
+        }).collect()
+    }
+}
+
+//{"letter": "α", "diacritics": ["smooth", "acute"]}, with "marks" only if there are any, and
+//"marks_before", the diacritics each mark is written before, only if a mark is not last
+#[derive(Serialize, Deserialize)]
+struct Letter {
+    letter: char,
+    #[serde(default)]
+    diacritics: Diacritics,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    marks: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    marks_before: Vec<Diacritics>
+}
+
+impl Serialize for HGKLetter {
+    fn serialize<S:Serializer>(&self, serializer:S) -> Result<S::Ok, S::Error> {
+        let mut marks_before = self.marks.iter_placed().map(|(_, ahead)| ahead).collect::<Vec<Diacritics>>();
+        if marks_before.iter().all(|a| a.is_empty()) {
+            marks_before.clear();
+        }
+        Letter { letter: self.letter, diacritics: self.diacritics, marks: self.marks.iter().collect(), marks_before }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HGKLetter {
+    fn deserialize<D:Deserializer<'de>>(deserializer:D) -> Result<HGKLetter, D::Error> {
+        let l = Letter::deserialize(deserializer)?;
+        let mut letter = HGKLetter::new(l.letter, l.diacritics);
+        if !l.marks_before.is_empty() && l.marks_before.len() != l.marks.chars().count() {
+            return Err(D::Error::custom("marks_before does not match marks"));
+        }
+        for (i, m) in l.marks.chars().enumerate() {
+            //as from gkletters(), marks are combining characters other than the ten diacritics
+            if !is_combining_mark(m) || hgk_is_combining(m) {
+                return Err(D::Error::custom(format!("invalid mark {:?}", m)));
+            }
+            if !letter.marks.push_before(m, l.marks_before.get(i).copied().unwrap_or(Diacritics::NONE)) {
+                return Err(D::Error::custom(format!("more than {} marks", HGK_MAX_MARKS)));
+            }
+        }
+        Ok(letter)
+    }
+}
+
+//the mapped keys only, e.g. {"a": "α", "b": "β", "/": "\u0301"}, diacritic keys as their
+//combining characters
+impl Serialize for HgkKeyboardLayout {
+    fn serialize<S:Serializer>(&self, serializer:S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map((' '..='~').filter_map(|k| match (self.get(k), self.diacritic(k)) {
+            (Some(v), _) => Some((k, String::from(v))),
+            (None, Some(d)) => Some((k, HGK_COMBINING_ORDER.iter().filter(|c| d.contains(c.1)).map(|c| c.0).collect::<String>())),
+            (None, None) => None
+        }))
+    }
+}
+
+impl<'de> Deserialize<'de> for HgkKeyboardLayout {
+    fn deserialize<D:Deserializer<'de>>(deserializer:D) -> Result<HgkKeyboardLayout, D::Error> {
+        let mut layout = HgkKeyboardLayout::empty();
+        for (k, v) in BTreeMap::<char, String>::deserialize(deserializer)? {
+            let mut chars = v.chars();
+            let ok = match (chars.next(), chars.next()) {
+                _ if !v.is_empty() && v.chars().all(hgk_is_combining) => layout.set_diacritic(k, v.chars().map(combining_diacritic).collect::<Diacritics>()),
+                (Some(c), None) => layout.set(k, c),
+                _ => return Err(D::Error::custom(format!("invalid value {:?}", v)))
+            };
+            if !ok {
+                return Err(D::Error::custom(format!("invalid key {:?}", k)));
+            }
+        }
+        Ok(layout)
+    }
+}
+
+//the engine's settings, its diacritic keys are in the layout; dead keys pending are not saved
+#[derive(Serialize, Deserialize)]
+struct InputEngine {
+    layout: HgkKeyboardLayout,
+    mode: HgkInputMode
+}
+
+impl Serialize for HgkInputEngine {
+    fn serialize<S:Serializer>(&self, serializer:S) -> Result<S::Ok, S::Error> {
+        InputEngine { layout: self.layout, mode: self.mode }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HgkInputEngine {
+    fn deserialize<D:Deserializer<'de>>(deserializer:D) -> Result<HgkInputEngine, D::Error> {
+        let e = InputEngine::deserialize(deserializer)?;
+        Ok(HgkInputEngine::new(e.layout, e.mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters() {
+        let l = HGKLetter::new('α', Diacritics::SMOOTH | Diacritics::ACUTE | Diacritics::IOTA_SUBSCRIPT);
+        let json = serde_json::to_string(&l).unwrap();
+        assert_eq!(json, r#"{"letter":"α","diacritics":["smooth","acute","iota_subscript"]}"#);
+        assert_eq!(serde_json::from_str::<HGKLetter>(&json).unwrap(), l);
+
+        let marked = "ε\u{0307}".parse::<HGKLetter>().unwrap();
+        let json = serde_json::to_string(&marked).unwrap();
+        assert_eq!(json, "{\"letter\":\"ε\",\"diacritics\":[],\"marks\":\"\u{0307}\"}");
+        assert_eq!(serde_json::from_str::<HGKLetter>(&json).unwrap(), marked);
+
+        assert_eq!(serde_json::from_str::<HGKLetter>(r#"{"letter":"β"}"#).unwrap(), HGKLetter::new('β', Diacritics::NONE));
+        assert!(serde_json::from_str::<HGKLetter>(r#"{"letter":"α","diacritics":["tonos"]}"#).is_err());
+        assert!(serde_json::from_str::<HGKLetter>(r#"{"letter":"αβ"}"#).is_err());
+        let placed = "α\u{0311}\u{0301}".parse::<HGKLetter>().unwrap();
+        let json = serde_json::to_string(&placed).unwrap();
+        assert_eq!(json, "{\"letter\":\"α\",\"diacritics\":[\"acute\"],\"marks\":\"\u{0311}\",\"marks_before\":[[\"acute\"]]}");
+        assert_eq!(serde_json::from_str::<HGKLetter>(&json).unwrap(), placed);
+        assert!(serde_json::from_str::<HGKLetter>("{\"letter\":\"α\",\"marks\":\"\u{0311}\",\"marks_before\":[[],[]]}").is_err());
+
+        assert!(serde_json::from_str::<HGKLetter>(r#"{"letter":"α","marks":"b"}"#).is_err());
+        assert!(serde_json::from_str::<HGKLetter>("{\"letter\":\"α\",\"marks\":\"\u{0301}\"}").is_err());
+        assert!(serde_json::from_str::<HGKLetter>("{\"letter\":\"α\",\"marks\":\"\u{0333}\u{0333}\u{0333}\u{0333}\u{0333}\"}").is_err());
+    }
+
+    #[test]
+    fn modes_and_settings() {
+        assert_eq!(serde_json::to_string(&HgkUnicodeMode::PrecomposedPUA).unwrap(), r#""PrecomposedPUA""#);
+        assert_eq!(serde_json::from_str::<HgkUnicodeMode>(r#""CombiningNFD""#).unwrap(), HgkUnicodeMode::CombiningNFD);
+        assert_eq!(serde_json::to_string(&HgkLetterType::HgkLongVowel).unwrap(), r#""HgkLongVowel""#);
+        assert_eq!(serde_json::to_string(&[HgkRomanization::Sbl]).unwrap(), r#"["Sbl"]"#);
+
+        let mut layout = HgkKeyboardLayout::empty();
+        layout.set('a', 'α');
+        layout.set('.', '·');
+        assert_eq!(serde_json::to_string(&layout).unwrap(), r#"{".":"·","a":"α"}"#);
+        layout.set_diacritic('/', Diacritics::ACUTE);
+        let json = serde_json::to_string(&layout).unwrap();
+        assert_eq!(json, "{\".\":\"·\",\"/\":\"\u{0301}\",\"a\":\"α\"}");
+        assert_eq!(serde_json::from_str::<HgkKeyboardLayout>(&json).unwrap(), layout);
+        assert_eq!(serde_json::from_str::<HgkKeyboardLayout>(&serde_json::to_string(&HOPLITE_LAYOUT).unwrap()).unwrap(), HOPLITE_LAYOUT);
+        assert!(serde_json::from_str::<HgkKeyboardLayout>(r#"{"a":"αβ"}"#).is_err());
+        assert!(serde_json::from_str::<HgkKeyboardLayout>(r#"{"é":"α"}"#).is_err());
+
+        let mut engine = HgkInputEngine::new(HOPLITE_LAYOUT, HgkInputMode::DeadKey);
+        engine.set_diacritic_key('q', Diacritics::MACRON);
+        let json = serde_json::to_string(&engine).unwrap();
+        let restored = serde_json::from_str::<HgkInputEngine>(&json).unwrap();
+        assert_eq!(restored.layout, engine.layout);
+        assert_eq!(restored.mode, HgkInputMode::DeadKey);
+        assert_eq!(restored.diacritic_for_key('q'), Some(Diacritics::MACRON));
+        assert_eq!(restored.diacritic_for_key('1'), engine.diacritic_for_key('1'));
+    }
+}
@@ -0,0 +1,279 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::*;
+
+//a letter-level edit, with enough information to undo it
+#[derive(PartialEq, Debug)]
+pub enum HgkEditOp {
+    Insert { index: usize, letter: HGKLetter },
+    Delete { index: usize, letter: HGKLetter },
+    ToggleDiacritic { index: usize, before: HGKLetter, after: HGKLetter },
+    ModeChange { before: HgkInputMode, after: HgkInputMode }
+}
+
+/// A keyboard editing session: a list of letters, a cursor and undo/redo history.
+#[derive(Debug)]
+pub struct HgkEditSession {
+    letters: Vec<HGKLetter>,
+    cursor: usize,
+    engine: HgkInputEngine,
+    undo_stack: Vec<Vec<HgkEditOp>>,
+    redo_stack: Vec<Vec<HgkEditOp>>,
+    group: Vec<HgkEditOp>,
+    group_depth: usize
+}
+
+impl HgkEditSession {
+    pub fn new(engine:HgkInputEngine) -> HgkEditSession {
+        HgkEditSession {
+            letters: Vec::new(),
+            cursor: 0,
+            engine,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            group: Vec::new(),
+            group_depth: 0
+        }
+    }
+
+    pub fn letters(&self) -> &[HGKLetter] {
+        &self.letters
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Cursor movement is not recorded in the history.
+    pub fn set_cursor(&mut self, cursor:usize) {
+        self.cursor = cmp::min(cursor, self.letters.len());
+    }
+
+    pub fn mode(&self) -> HgkInputMode {
+        self.engine.mode
+    }
+
+    pub fn to_string<'a>(&self, unicode_mode:impl Into<HgkOutputMode<'a>>) -> String {
+        let unicode_mode = unicode_mode.into();
+        self.letters.iter().map(|l| l.to_string(unicode_mode)).collect::<String>()
+    }
+
+    /// Operations recorded until the matching end_group() are undone and redone together.
+    /// Groups may be nested.
+    pub fn begin_group(&mut self) {
+        self.group_depth += 1;
+    }
+
+    pub fn end_group(&mut self) {
+        if self.group_depth == 0 {
+            return;
+        }
+        self.group_depth -= 1;
+        if self.group_depth == 0 && !self.group.is_empty() {
+            let ops = core::mem::take(&mut self.group);
+            self.undo_stack.push(ops);
+        }
+    }
+
+    fn record(&mut self, op:HgkEditOp) {
+        self.redo_stack.clear();
+        if self.group_depth > 0 {
+            self.group.push(op);
+        }
+        else {
+            self.undo_stack.push(vec![op]);
+        }
+    }
+
+    pub fn insert(&mut self, letter:HGKLetter) {
+        let index = self.cursor;
+        self.letters.insert(index, letter);
+        self.cursor += 1;
+        self.record(HgkEditOp::Insert { index, letter });
+    }
+
+    /// Delete the letter before the cursor.  Returns false if there is nothing to delete.
+    pub fn delete_backward(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        let index = self.cursor;
+        let letter = self.letters.remove(index);
+        self.record(HgkEditOp::Delete { index, letter });
+        true
+    }
+
+    /// Toggle a diacritic on the letter before the cursor.  Returns false if nothing changed.
+    pub fn toggle_diacritic(&mut self, d:impl Into<Diacritics>, on_only:bool) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        let index = self.cursor - 1;
+        let before = self.letters[index];
+        self.letters[index].toggle_diacritic(d, on_only);
+        if self.letters[index] == before {
+            return false;
+        }
+        let after = self.letters[index];
+        self.record(HgkEditOp::ToggleDiacritic { index, before, after });
+        true
+    }
+
+    pub fn set_mode(&mut self, mode:HgkInputMode) {
+        let before = self.engine.mode;
+        if before == mode {
+            return;
+        }
+        self.engine.set_mode(mode);
+        self.record(HgkEditOp::ModeChange { before, after: mode });
+    }
+
+    /// Send a key to the input engine and apply the result to the session.
+    pub fn key_press(&mut self, key:char) -> HgkKeyResult {
+        let previous = if self.cursor > 0 { Some(&self.letters[self.cursor - 1]) } else { None };
+        let res = self.engine.key_press(key, previous);
+        match &res {
+            HgkKeyResult::Insert(l) | HgkKeyResult::InsertRejected(l, _, _) => self.insert(*l),
+            HgkKeyResult::Replace(l) => {
+                let index = self.cursor - 1;
+                let before = core::mem::replace(&mut self.letters[index], *l);
+                self.record(HgkEditOp::ToggleDiacritic { index, before, after: *l });
+            },
+            _ => ()
+        }
+        res
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        //an open group is closed before undoing
+        if self.group_depth > 0 {
+            self.group_depth = 1;
+            self.end_group();
+        }
+        match self.undo_stack.pop() {
+            Some(ops) => {
+                for op in ops.iter().rev() {
+                    self.apply(op, true);
+                }
+                self.redo_stack.push(ops);
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(ops) => {
+                for op in ops.iter() {
+                    self.apply(op, false);
+                }
+                self.undo_stack.push(ops);
+                true
+            },
+            None => false
+        }
+    }
+
+    fn apply(&mut self, op:&HgkEditOp, reverse:bool) {
+        match (op, reverse) {
+            (HgkEditOp::Insert { index, letter }, false) | (HgkEditOp::Delete { index, letter }, true) => {
+                self.letters.insert(*index, *letter);
+                self.cursor = index + 1;
+            },
+            (HgkEditOp::Insert { index, .. }, true) | (HgkEditOp::Delete { index, .. }, false) => {
+                self.letters.remove(*index);
+                self.cursor = *index;
+            },
+            (HgkEditOp::ToggleDiacritic { index, before, after }, _) => {
+                self.letters[*index] = if reverse { *before } else { *after };
+                self.cursor = index + 1;
+            },
+            (HgkEditOp::ModeChange { before, after }, _) => {
+                self.engine.set_mode(if reverse { *before } else { *after });
+            }
+        }
+    }
+}
+
+impl Default for HgkEditSession {
+    fn default() -> HgkEditSession {
+        HgkEditSession::new(HgkInputEngine::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_redo() {
+        let mut s = HgkEditSession::default();
+        for k in "a6".chars() {
+            s.key_press(k);
+        }
+        assert_eq!(s.to_string(HgkUnicodeMode::Precomposed), "ᾱ");
+
+        //circumflex turns off the macron, undo brings it back
+        assert!(s.toggle_diacritic(Diacritics::CIRCUMFLEX, false));
+        assert_eq!(s.to_string(HgkUnicodeMode::Precomposed), "ᾶ");
+        assert!(s.undo());
+        assert_eq!(s.letters()[0], HGKLetter::new('α', Diacritics::MACRON));
+        assert!(s.redo());
+        assert_eq!(s.letters()[0], HGKLetter::new('α', Diacritics::CIRCUMFLEX));
+
+        //acute is not legal on beta: nothing recorded
+        s.key_press('b');
+        assert!(!s.toggle_diacritic(Diacritics::ACUTE, false));
+        assert!(s.delete_backward());
+        assert_eq!(s.to_string(HgkUnicodeMode::Precomposed), "ᾶ");
+        assert!(s.undo());
+        assert_eq!(s.to_string(HgkUnicodeMode::Precomposed), "ᾶβ");
+        assert_eq!(s.cursor(), 2);
+        assert!(s.undo());
+        assert!(s.undo());
+        assert!(s.undo());
+        assert!(s.undo());
+        assert_eq!(s.to_string(HgkUnicodeMode::Precomposed), "");
+        assert!(!s.undo());
+        assert!(s.redo());
+        assert_eq!(s.to_string(HgkUnicodeMode::Precomposed), "α");
+
+        //a new edit clears the redo history
+        s.key_press('b');
+        assert!(!s.can_redo());
+    }
+
+    #[test]
+    fn grouped_undo() {
+        let mut s = HgkEditSession::default();
+        s.begin_group();
+        s.set_mode(HgkInputMode::DeadKey);
+        for k in "3ab".chars() {
+            s.key_press(k);
+        }
+        s.end_group();
+        assert_eq!(s.to_string(HgkUnicodeMode::Precomposed), "άβ");
+        assert_eq!(s.mode(), HgkInputMode::DeadKey);
+
+        assert!(s.undo());
+        assert_eq!(s.to_string(HgkUnicodeMode::Precomposed), "");
+        assert_eq!(s.mode(), HgkInputMode::Postfix);
+        assert!(!s.can_undo());
+
+        assert!(s.redo());
+        assert_eq!(s.to_string(HgkUnicodeMode::Precomposed), "άβ");
+        assert_eq!(s.mode(), HgkInputMode::DeadKey);
+        assert_eq!(s.cursor(), 2);
+    }
+}
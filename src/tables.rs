@@ -1,3 +1,5 @@
+use crate::diacritics::Diacritics;
+
 pub const HGK_NO_DIACRITICS :u32 = 0x000;
 pub const HGK_ROUGH         :u32 = 0x001;
 pub const HGK_SMOOTH        :u32 = 0x002;
@@ -230,6 +232,215 @@ pub(crate) const GREEK_LOWER: &[char] = &[
 '\u{03B6}'
 ];
 
+//beta code style: q = theta, c = xi, v = digamma, j = final sigma
+pub(crate) const BETA_CODE_LOWER: &[char] = &[
+'\u{03B1}',
+'\u{03B2}',
+'\u{03BE}',
+'\u{03B4}',
+'\u{03B5}',
+'\u{03C6}',
+'\u{03B3}',
+'\u{03B7}',
+'\u{03B9}',
+'\u{03C2}',
+'\u{03BA}',
+'\u{03BB}',
+'\u{03BC}',
+'\u{03BD}',
+'\u{03BF}',
+'\u{03C0}',
+'\u{03B8}',
+'\u{03C1}',
+'\u{03C3}',
+'\u{03C4}',
+'\u{03C5}',
+'\u{03DD}',
+'\u{03C9}',
+'\u{03C7}',
+'\u{03C8}',
+'\u{03B6}'
+];
+
+pub(crate) const BETA_CODE_UPPER: &[char] = &[
+'\u{0391}',
+'\u{0392}',
+'\u{039E}',
+'\u{0394}',
+'\u{0395}',
+'\u{03A6}',
+'\u{0393}',
+'\u{0397}',
+'\u{0399}',
+'\u{0000}',
+'\u{039A}',
+'\u{039B}',
+'\u{039C}',
+'\u{039D}',
+'\u{039F}',
+'\u{03A0}',
+'\u{0398}',
+'\u{03A1}',
+'\u{03A3}',
+'\u{03A4}',
+'\u{03A5}',
+'\u{03DC}',
+'\u{03A9}',
+'\u{03A7}',
+'\u{03A8}',
+'\u{0396}'
+];
+
+//SGreek font: c = chi, x = xi, q = theta, j = final sigma
+pub(crate) const SGREEK_LOWER: &[char] = &[
+'\u{03B1}',
+'\u{03B2}',
+'\u{03C7}',
+'\u{03B4}',
+'\u{03B5}',
+'\u{03C6}',
+'\u{03B3}',
+'\u{03B7}',
+'\u{03B9}',
+'\u{03C2}',
+'\u{03BA}',
+'\u{03BB}',
+'\u{03BC}',
+'\u{03BD}',
+'\u{03BF}',
+'\u{03C0}',
+'\u{03B8}',
+'\u{03C1}',
+'\u{03C3}',
+'\u{03C4}',
+'\u{03C5}',
+'\u{03DD}',
+'\u{03C9}',
+'\u{03BE}',
+'\u{03C8}',
+'\u{03B6}'
+];
+
+pub(crate) const SGREEK_UPPER: &[char] = &[
+'\u{0391}',
+'\u{0392}',
+'\u{03A7}',
+'\u{0394}',
+'\u{0395}',
+'\u{03A6}',
+'\u{0393}',
+'\u{0397}',
+'\u{0399}',
+'\u{0000}',
+'\u{039A}',
+'\u{039B}',
+'\u{039C}',
+'\u{039D}',
+'\u{039F}',
+'\u{03A0}',
+'\u{0398}',
+'\u{03A1}',
+'\u{03A3}',
+'\u{03A4}',
+'\u{03A5}',
+'\u{03DC}',
+'\u{03A9}',
+'\u{039E}',
+'\u{03A8}',
+'\u{0396}'
+];
+
+//standard modern Greek keyboard
+pub(crate) const MODERN_GREEK_LOWER: &[char] = &[
+'\u{03B1}',
+'\u{03B2}',
+'\u{03C8}',
+'\u{03B4}',
+'\u{03B5}',
+'\u{03C6}',
+'\u{03B3}',
+'\u{03B7}',
+'\u{03B9}',
+'\u{03BE}',
+'\u{03BA}',
+'\u{03BB}',
+'\u{03BC}',
+'\u{03BD}',
+'\u{03BF}',
+'\u{03C0}',
+'\u{003B}',
+'\u{03C1}',
+'\u{03C3}',
+'\u{03C4}',
+'\u{03B8}',
+'\u{03C9}',
+'\u{03C2}',
+'\u{03C7}',
+'\u{03C5}',
+'\u{03B6}'
+];
+
+pub(crate) const MODERN_GREEK_UPPER: &[char] = &[
+'\u{0391}',
+'\u{0392}',
+'\u{03A8}',
+'\u{0394}',
+'\u{0395}',
+'\u{03A6}',
+'\u{0393}',
+'\u{0397}',
+'\u{0399}',
+'\u{039E}',
+'\u{039A}',
+'\u{039B}',
+'\u{039C}',
+'\u{039D}',
+'\u{039F}',
+'\u{03A0}',
+'\u{003A}',
+'\u{03A1}',
+'\u{03A3}',
+'\u{03A4}',
+'\u{0398}',
+'\u{03A9}',
+'\u{0385}',
+'\u{03A7}',
+'\u{03A5}',
+'\u{0396}'
+];
+
+//number keys enter diacritics
+pub(crate) const HOPLITE_DIACRITIC_KEYS: &[(char, Diacritics)] = &[
+    ('1', Diacritics::ROUGH),
+    ('2', Diacritics::SMOOTH),
+    ('3', Diacritics::ACUTE),
+    ('4', Diacritics::GRAVE),
+    ('5', Diacritics::CIRCUMFLEX),
+    ('6', Diacritics::MACRON),
+    ('7', Diacritics::BREVE),
+    ('8', Diacritics::IOTA_SUBSCRIPT),
+    ('9', Diacritics::DIAERESIS),
+    ('0', Diacritics::UNDERDOT)
+];
+
+//beta code diacritics follow the letter
+pub(crate) const BETA_CODE_DIACRITIC_KEYS: &[(char, Diacritics)] = &[
+    (')', Diacritics::SMOOTH),
+    ('(', Diacritics::ROUGH),
+    ('/', Diacritics::ACUTE),
+    ('\\', Diacritics::GRAVE),
+    ('=', Diacritics::CIRCUMFLEX),
+    ('+', Diacritics::DIAERESIS),
+    ('|', Diacritics::IOTA_SUBSCRIPT),
+    ('?', Diacritics::UNDERDOT)
+];
+
+//the tonos and dialytika dead keys of the greek keyboard
+pub(crate) const MODERN_GREEK_DIACRITIC_KEYS: &[(char, Diacritics)] = &[
+    (';', Diacritics::ACUTE),
+    (':', Diacritics::DIAERESIS)
+];
+
 pub(crate) const GREEK_LOWER_PUA: &[char] = &[
 '\u{EB04}',//alpha
 '\u{EB07}',
@@ -281,6 +492,83 @@ pub(crate) const GREEK_LOWER_PUA: &[char] = &[
 '\u{EB83}'
 ];
 
+//alphabetic numerals: units, tens and hundreds, each indexed by digit - 1
+pub(crate) const GREEK_NUMERALS_LOWER: [[char; 9]; 3] = [
+    ['α', 'β', 'γ', 'δ', 'ε', '\u{03DB}', 'ζ', 'η', 'θ'], //stigma
+    ['ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'ο', 'π', '\u{03DF}'], //koppa
+    ['ρ', 'σ', 'τ', 'υ', 'φ', 'χ', 'ψ', 'ω', '\u{03E1}']  //sampi
+];
+
+pub(crate) const GREEK_NUMERALS_UPPER: [[char; 9]; 3] = [
+    ['Α', 'Β', 'Γ', 'Δ', 'Ε', '\u{03DA}', 'Ζ', 'Η', 'Θ'],
+    ['Ι', 'Κ', 'Λ', 'Μ', 'Ν', 'Ξ', 'Ο', 'Π', '\u{03DE}'],
+    ['Ρ', 'Σ', 'Τ', 'Υ', 'Φ', 'Χ', 'Ψ', 'Ω', '\u{03E0}']
+];
+
+//the combining characters of the diacritics in the order HGKLetter::to_string() writes them
+pub(crate) const HGK_COMBINING_ORDER: &[(char, Diacritics)] = &[
+    ('\u{0304}', Diacritics::MACRON), ('\u{0306}', Diacritics::BREVE), ('\u{0308}', Diacritics::DIAERESIS),
+    ('\u{0314}', Diacritics::ROUGH), ('\u{0313}', Diacritics::SMOOTH), ('\u{0301}', Diacritics::ACUTE),
+    ('\u{0300}', Diacritics::GRAVE), ('\u{0342}', Diacritics::CIRCUMFLEX), ('\u{0345}', Diacritics::IOTA_SUBSCRIPT),
+    ('\u{0323}', Diacritics::UNDERDOT)
+];
+
+//other forms accepted when parsing numerals
+pub(crate) const GREEK_NUMERAL_VARIANTS: &[(char, u32)] = &[
+    ('\u{03DD}', 6), ('\u{03DC}', 6),     //digamma
+    ('\u{03D9}', 90), ('\u{03D8}', 90),   //archaic koppa
+    ('\u{0373}', 900), ('\u{0372}', 900), //archaic sampi
+    ('ς', 6)                              //final sigma written for stigma
+];
+
+//romanization of the lower case letters, shared by all schemes
+pub(crate) const ROMAN_LETTERS: &[(char, &str)] = &[
+    ('α', "a"), ('β', "b"), ('γ', "g"), ('δ', "d"), ('ε', "e"), ('ϝ', "w"), ('ζ', "z"), ('η', "e"),
+    ('θ', "th"), ('ι', "i"), ('κ', "k"), ('λ', "l"), ('μ', "m"), ('ν', "n"), ('ξ', "x"), ('ο', "o"),
+    ('π', "p"), ('ρ', "r"), ('σ', "s"), ('ς', "s"), ('τ', "t"), ('υ', "y"), ('φ', "ph"), ('χ', "ch"),
+    ('ψ', "ps"), ('ω', "o")
+];
+
+//latin and cyrillic letters which look like greek letters, and the greek letter
+pub(crate) const HOMOGLYPHS: &[(char, char)] = &[
+    //latin
+    ('a', 'α'), ('i', 'ι'), ('k', 'κ'), ('o', 'ο'), ('p', 'ρ'), ('u', 'υ'), ('v', 'ν'), ('x', 'χ'),
+    ('A', 'Α'), ('B', 'Β'), ('E', 'Ε'), ('H', 'Η'), ('I', 'Ι'), ('K', 'Κ'), ('M', 'Μ'), ('N', 'Ν'),
+    ('O', 'Ο'), ('P', 'Ρ'), ('T', 'Τ'), ('X', 'Χ'), ('Y', 'Υ'), ('Z', 'Ζ'),
+    //cyrillic
+    ('\u{0430}', 'α'), ('\u{043A}', 'κ'), ('\u{043E}', 'ο'), ('\u{043F}', 'π'), ('\u{0440}', 'ρ'), ('\u{0444}', 'φ'),
+    ('\u{0445}', 'χ'), ('\u{0456}', 'ι'),
+    ('\u{0410}', 'Α'), ('\u{0412}', 'Β'), ('\u{0413}', 'Γ'), ('\u{0415}', 'Ε'), ('\u{041A}', 'Κ'), ('\u{041C}', 'Μ'),
+    ('\u{041D}', 'Η'), ('\u{041E}', 'Ο'), ('\u{041F}', 'Π'), ('\u{0420}', 'Ρ'), ('\u{0422}', 'Τ'), ('\u{0424}', 'Φ'),
+    ('\u{0425}', 'Χ'), ('\u{0406}', 'Ι')
+];
+
+//common elided forms and their full forms.  Keys use U+2019 as the apostrophe
+pub(crate) const ELISIONS: &[(&str, &str)] = &[
+    ("ἀλλ’", "ἀλλά"), ("ἀνθ’", "ἀντί"), ("ἀντ’", "ἀντί"), ("ἀπ’", "ἀπό"), ("ἀφ’", "ἀπό"), ("γ’", "γε"),
+    ("δ’", "δέ"), ("δι’", "διά"), ("ἐπ’", "ἐπί"), ("ἐφ’", "ἐπί"), ("ἐστ’", "ἐστί"), ("καθ’", "κατά"),
+    ("κατ’", "κατά"), ("μεθ’", "μετά"), ("μετ’", "μετά"), ("μηδ’", "μηδέ"), ("οὐδ’", "οὐδέ"), ("παρ’", "παρά"),
+    ("ποτ’", "ποτε"), ("τ’", "τε"), ("ταῦτ’", "ταῦτα"), ("τοῦτ’", "τοῦτο"), ("ὑπ’", "ὑπό"), ("ὑφ’", "ὑπό")
+];
+
+//common crasis forms and the words they join
+pub(crate) const CRASES: &[(&str, &str)] = &[
+    ("κἀγώ", "καὶ ἐγώ"), ("κἀμοί", "καὶ ἐμοί"), ("κἀμέ", "καὶ ἐμέ"), ("κἀκεῖνος", "καὶ ἐκεῖνος"), ("κἀκεῖ", "καὶ ἐκεῖ"),
+    ("κἄν", "καὶ ἄν"), ("κἄν", "καὶ ἐάν"), ("κἀν", "καὶ ἐν"), ("κἀγαθός", "καὶ ἀγαθός"), ("χἠ", "καὶ ἡ"),
+    ("χοἰ", "καὶ οἱ"), ("τἆλλα", "τὰ ἄλλα"), ("τἀγαθά", "τὰ ἀγαθά"), ("τἀληθῆ", "τὰ ἀληθῆ"), ("ταὐτό", "τὸ αὐτό"),
+    ("ταὐτά", "τὰ αὐτά"), ("τοὔνομα", "τὸ ὄνομα"), ("τοὐναντίον", "τὸ ἐναντίον"), ("θοὔδωρ", "τὸ ὕδωρ"),
+    ("θοἰμάτιον", "τὸ ἱμάτιον"),
+    ("ἁνήρ", "ὁ ἀνήρ"), ("ἅνθρωπος", "ὁ ἄνθρωπος"), ("ὦνθρωπε", "ὦ ἄνθρωπε")
+];
+
+//monosyllables which keep their accent in monotonic, and whether a grave counts
+pub(crate) const MONOTONIC_ACCENTED_MONOSYLLABLES: &[(&str, bool)] = &[
+    ("ή", true), ("πού", false), ("πώς", false)
+];
+
+pub const HGK_KERAIA:char = '\u{0374}';
+pub const HGK_LOWER_KERAIA:char = '\u{0375}';
+
 pub const NOT_ACCENTABLE_CHAR :char = '\u{0001}'; //dummy value
 pub const NOCHAR              :u32 = 0;
 
@@ -312,6 +600,7 @@ const HGK_PHI_SORT:u32 = 24;
 const HGK_CHI_SORT:u32 = 25;
 const HGK_PSI_SORT:u32 = 26;
 const HGK_OMEGA_SORT:u32 = 27;
+const HGK_SAMPI_SORT:u32 = 28;         //numeral 900
 
 
 //first col will be the actual codepoint for an accenting character
@@ -320,10 +609,10 @@ const HGK_OMEGA_SORT:u32 = 27;
 pub(crate) const GREEK_BASIC: &[(char, u32, u32)] = &[
     /* 0370 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ), //capital heta
     /* 0371 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ), //lower case heta
-    /* 0372 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ), //capital sampi
-    /* 0373 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ), //lower case sampi
-    /* 0374 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
-    /* 0375 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0372 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SAMPI_SORT ), //capital archaic sampi
+    /* 0373 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SAMPI_SORT ), //lower case archaic sampi
+    /* 0374 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ), //keraia, punctuation
+    /* 0375 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ), //lower keraia, punctuation
     /* 0376 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
     /* 0377 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
     /* 0378 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
@@ -422,16 +711,16 @@ pub(crate) const GREEK_BASIC: &[(char, u32, u32)] = &[
     /* 03D5 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
     /* 03D6 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
     /* 03D7 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
-    /* 03D8 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
-    /* 03D9 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
-    /* 03DA */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
-    /* 03DB */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03D8 */ ( '\u{03D8}', 0, HGK_KOPPA_SORT ), /* ARCHAIC KOPPA */
+    /* 03D9 */ ( '\u{03D9}', 0, HGK_KOPPA_SORT ), /* SMALL ARCHAIC KOPPA */
+    /* 03DA */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_DIGAMMA_SORT ), /* STIGMA, the numeral 6 */
+    /* 03DB */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_DIGAMMA_SORT ), /* SMALL STIGMA */
     /* 03DC */ ( '\u{03DC}', 0, HGK_DIGAMMA_SORT ), /* DIGAMMA */
     /* 03DD */ ( '\u{03DD}', 0, HGK_DIGAMMA_SORT ), /* SMALL DIGAMMA */
     /* 03DE */ ( '\u{03DE}', 0, HGK_KOPPA_SORT ), /* KOPPA */
     /* 03DF */ ( '\u{03DF}', 0, HGK_KOPPA_SORT ), /* SMALL KOPPA */
-    /* 03E0 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
-    /* 03E1 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03E0 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SAMPI_SORT ), /* SAMPI */
+    /* 03E1 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SAMPI_SORT ), /* SMALL SAMPI */
     /* 03E2 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
     /* 03E3 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
     /* 03E4 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
@@ -1062,3 +1351,34 @@ pub(crate) const GREEK_PRECOMPOSED: &[[char; 2]; 28] = &[
     }
 ];
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    //generated by examples/gen_tables.rs from unicode/UnicodeData-greek.txt
+    mod generated {
+        use super::super::*;
+        include!("tables_generated.rs");
+    }
+
+    #[test]
+    fn tables_match_unicode_data() {
+        let mut diffs:Vec<String> = Vec::new();
+        for (i, (hand, gen)) in GREEK_BASIC.iter().zip(generated::GENERATED_GREEK_BASIC).enumerate() {
+            if hand != gen {
+                diffs.push(format!("{:04X}: {:?} generated {:?}", 0x0370 + i, hand, gen));
+            }
+        }
+        for (i, (hand, gen)) in GREEK_EXTENDED.iter().zip(generated::GENERATED_GREEK_EXTENDED).enumerate() {
+            if hand != gen {
+                diffs.push(format!("{:04X}: {:?} generated {:?}", 0x1F00 + i, hand, gen));
+            }
+        }
+        assert_eq!(GREEK_BASIC.len(), generated::GENERATED_GREEK_BASIC.len());
+        assert_eq!(GREEK_EXTENDED.len(), generated::GENERATED_GREEK_EXTENDED.len());
+        assert!(diffs.is_empty(), "tables differ from src/tables_generated.rs:\n{}", diffs.join("\n"));
+    }
+}
@@ -0,0 +1,407 @@
+//generated by examples/gen_tables.rs from UnicodeData.txt, do not edit
+
+pub(crate) const GENERATED_GREEK_BASIC: &[(char, u32, u32)] = &[
+    /* 0370 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0371 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0372 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SAMPI_SORT ),
+    /* 0373 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SAMPI_SORT ),
+    /* 0374 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0375 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0376 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0377 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0378 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0379 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 037A */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 037B */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 037C */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 037D */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 037E */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 037F */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0380 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0381 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0382 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0383 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0384 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0385 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0386 */ ( '\u{0391}', HGK_ACUTE, HGK_ALPHA_SORT ),
+    /* 0387 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 0388 */ ( '\u{0395}', HGK_ACUTE, HGK_EPSILON_SORT ),
+    /* 0389 */ ( '\u{0397}', HGK_ACUTE, HGK_ETA_SORT ),
+    /* 038A */ ( '\u{0399}', HGK_ACUTE, HGK_IOTA_SORT ),
+    /* 038B */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 038C */ ( '\u{039F}', HGK_ACUTE, HGK_OMICRON_SORT ),
+    /* 038D */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 038E */ ( '\u{03A5}', HGK_ACUTE, HGK_UPSILON_SORT ),
+    /* 038F */ ( '\u{03A9}', HGK_ACUTE, HGK_OMEGA_SORT ),
+    /* 0390 */ ( '\u{03B9}', HGK_ACUTE | HGK_DIAERESIS, HGK_IOTA_SORT ),
+    /* 0391 */ ( '\u{0391}', HGK_NO_DIACRITICS, HGK_ALPHA_SORT ),
+    /* 0392 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_BETA_SORT ),
+    /* 0393 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_GAMMA_SORT ),
+    /* 0394 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_DELTA_SORT ),
+    /* 0395 */ ( '\u{0395}', HGK_NO_DIACRITICS, HGK_EPSILON_SORT ),
+    /* 0396 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_ZETA_SORT ),
+    /* 0397 */ ( '\u{0397}', HGK_NO_DIACRITICS, HGK_ETA_SORT ),
+    /* 0398 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_THETA_SORT ),
+    /* 0399 */ ( '\u{0399}', HGK_NO_DIACRITICS, HGK_IOTA_SORT ),
+    /* 039A */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_KAPPA_SORT ),
+    /* 039B */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_LAMBDA_SORT ),
+    /* 039C */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_MU_SORT ),
+    /* 039D */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_NU_SORT ),
+    /* 039E */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_XI_SORT ),
+    /* 039F */ ( '\u{039F}', HGK_NO_DIACRITICS, HGK_OMICRON_SORT ),
+    /* 03A0 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_PI_SORT ),
+    /* 03A1 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_RHO_SORT ),
+    /* 03A2 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03A3 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SIGMA_SORT ),
+    /* 03A4 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_TAU_SORT ),
+    /* 03A5 */ ( '\u{03A5}', HGK_NO_DIACRITICS, HGK_UPSILON_SORT ),
+    /* 03A6 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_PHI_SORT ),
+    /* 03A7 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_CHI_SORT ),
+    /* 03A8 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_PSI_SORT ),
+    /* 03A9 */ ( '\u{03A9}', HGK_NO_DIACRITICS, HGK_OMEGA_SORT ),
+    /* 03AA */ ( '\u{0399}', HGK_DIAERESIS, HGK_IOTA_SORT ),
+    /* 03AB */ ( '\u{03A5}', HGK_DIAERESIS, HGK_UPSILON_SORT ),
+    /* 03AC */ ( '\u{03B1}', HGK_ACUTE, HGK_ALPHA_SORT ),
+    /* 03AD */ ( '\u{03B5}', HGK_ACUTE, HGK_EPSILON_SORT ),
+    /* 03AE */ ( '\u{03B7}', HGK_ACUTE, HGK_ETA_SORT ),
+    /* 03AF */ ( '\u{03B9}', HGK_ACUTE, HGK_IOTA_SORT ),
+    /* 03B0 */ ( '\u{03C5}', HGK_ACUTE | HGK_DIAERESIS, HGK_UPSILON_SORT ),
+    /* 03B1 */ ( '\u{03B1}', HGK_NO_DIACRITICS, HGK_ALPHA_SORT ),
+    /* 03B2 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_BETA_SORT ),
+    /* 03B3 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_GAMMA_SORT ),
+    /* 03B4 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_DELTA_SORT ),
+    /* 03B5 */ ( '\u{03B5}', HGK_NO_DIACRITICS, HGK_EPSILON_SORT ),
+    /* 03B6 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_ZETA_SORT ),
+    /* 03B7 */ ( '\u{03B7}', HGK_NO_DIACRITICS, HGK_ETA_SORT ),
+    /* 03B8 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_THETA_SORT ),
+    /* 03B9 */ ( '\u{03B9}', HGK_NO_DIACRITICS, HGK_IOTA_SORT ),
+    /* 03BA */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_KAPPA_SORT ),
+    /* 03BB */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_LAMBDA_SORT ),
+    /* 03BC */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_MU_SORT ),
+    /* 03BD */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_NU_SORT ),
+    /* 03BE */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_XI_SORT ),
+    /* 03BF */ ( '\u{03BF}', HGK_NO_DIACRITICS, HGK_OMICRON_SORT ),
+    /* 03C0 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_PI_SORT ),
+    /* 03C1 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_RHO_SORT ),
+    /* 03C2 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SIGMA_SORT ),
+    /* 03C3 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SIGMA_SORT ),
+    /* 03C4 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_TAU_SORT ),
+    /* 03C5 */ ( '\u{03C5}', HGK_NO_DIACRITICS, HGK_UPSILON_SORT ),
+    /* 03C6 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_PHI_SORT ),
+    /* 03C7 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_CHI_SORT ),
+    /* 03C8 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_PSI_SORT ),
+    /* 03C9 */ ( '\u{03C9}', HGK_NO_DIACRITICS, HGK_OMEGA_SORT ),
+    /* 03CA */ ( '\u{03B9}', HGK_DIAERESIS, HGK_IOTA_SORT ),
+    /* 03CB */ ( '\u{03C5}', HGK_DIAERESIS, HGK_UPSILON_SORT ),
+    /* 03CC */ ( '\u{03BF}', HGK_ACUTE, HGK_OMICRON_SORT ),
+    /* 03CD */ ( '\u{03C5}', HGK_ACUTE, HGK_UPSILON_SORT ),
+    /* 03CE */ ( '\u{03C9}', HGK_ACUTE, HGK_OMEGA_SORT ),
+    /* 03CF */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03D0 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03D1 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03D2 */ ( '\u{03D2}', HGK_NO_DIACRITICS, HGK_UPSILON_SORT ),
+    /* 03D3 */ ( '\u{03D2}', HGK_ACUTE, HGK_UPSILON_SORT ),
+    /* 03D4 */ ( '\u{03D2}', HGK_DIAERESIS, HGK_UPSILON_SORT ),
+    /* 03D5 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03D6 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03D7 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03D8 */ ( '\u{03D8}', HGK_NO_DIACRITICS, HGK_KOPPA_SORT ),
+    /* 03D9 */ ( '\u{03D9}', HGK_NO_DIACRITICS, HGK_KOPPA_SORT ),
+    /* 03DA */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_DIGAMMA_SORT ),
+    /* 03DB */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_DIGAMMA_SORT ),
+    /* 03DC */ ( '\u{03DC}', HGK_NO_DIACRITICS, HGK_DIGAMMA_SORT ),
+    /* 03DD */ ( '\u{03DD}', HGK_NO_DIACRITICS, HGK_DIGAMMA_SORT ),
+    /* 03DE */ ( '\u{03DE}', HGK_NO_DIACRITICS, HGK_KOPPA_SORT ),
+    /* 03DF */ ( '\u{03DF}', HGK_NO_DIACRITICS, HGK_KOPPA_SORT ),
+    /* 03E0 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SAMPI_SORT ),
+    /* 03E1 */ ( NOT_ACCENTABLE_CHAR, NOCHAR, HGK_SAMPI_SORT ),
+    /* 03E2 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03E3 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03E4 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03E5 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03E6 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03E7 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03E8 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03E9 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03EA */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03EB */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03EC */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03ED */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03EE */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03EF */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F0 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F1 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F2 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F3 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F4 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F5 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F6 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F7 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F8 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03F9 */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03FA */ ( '\u{03FA}', HGK_NO_DIACRITICS, HGK_SAN_SORT ),
+    /* 03FB */ ( '\u{03FB}', HGK_NO_DIACRITICS, HGK_SAN_SORT ),
+    /* 03FC */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03FD */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03FE */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+    /* 03FF */ ( '\u{0000}', HGK_NO_DIACRITICS, HGK_NO_SORT ),
+];
+
+pub(crate) const GENERATED_GREEK_EXTENDED: &[(char, u32)] = &[
+    /* 1F00 */ ( '\u{03B1}', HGK_SMOOTH ),
+    /* 1F01 */ ( '\u{03B1}', HGK_ROUGH ),
+    /* 1F02 */ ( '\u{03B1}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F03 */ ( '\u{03B1}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F04 */ ( '\u{03B1}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F05 */ ( '\u{03B1}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F06 */ ( '\u{03B1}', HGK_SMOOTH | HGK_CIRCUMFLEX ),
+    /* 1F07 */ ( '\u{03B1}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F08 */ ( '\u{0391}', HGK_SMOOTH ),
+    /* 1F09 */ ( '\u{0391}', HGK_ROUGH ),
+    /* 1F0A */ ( '\u{0391}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F0B */ ( '\u{0391}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F0C */ ( '\u{0391}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F0D */ ( '\u{0391}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F0E */ ( '\u{0391}', HGK_SMOOTH | HGK_CIRCUMFLEX ),
+    /* 1F0F */ ( '\u{0391}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F10 */ ( '\u{03B5}', HGK_SMOOTH ),
+    /* 1F11 */ ( '\u{03B5}', HGK_ROUGH ),
+    /* 1F12 */ ( '\u{03B5}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F13 */ ( '\u{03B5}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F14 */ ( '\u{03B5}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F15 */ ( '\u{03B5}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F16 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F17 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F18 */ ( '\u{0395}', HGK_SMOOTH ),
+    /* 1F19 */ ( '\u{0395}', HGK_ROUGH ),
+    /* 1F1A */ ( '\u{0395}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F1B */ ( '\u{0395}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F1C */ ( '\u{0395}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F1D */ ( '\u{0395}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F1E */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F1F */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F20 */ ( '\u{03B7}', HGK_SMOOTH ),
+    /* 1F21 */ ( '\u{03B7}', HGK_ROUGH ),
+    /* 1F22 */ ( '\u{03B7}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F23 */ ( '\u{03B7}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F24 */ ( '\u{03B7}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F25 */ ( '\u{03B7}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F26 */ ( '\u{03B7}', HGK_SMOOTH | HGK_CIRCUMFLEX ),
+    /* 1F27 */ ( '\u{03B7}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F28 */ ( '\u{0397}', HGK_SMOOTH ),
+    /* 1F29 */ ( '\u{0397}', HGK_ROUGH ),
+    /* 1F2A */ ( '\u{0397}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F2B */ ( '\u{0397}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F2C */ ( '\u{0397}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F2D */ ( '\u{0397}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F2E */ ( '\u{0397}', HGK_SMOOTH | HGK_CIRCUMFLEX ),
+    /* 1F2F */ ( '\u{0397}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F30 */ ( '\u{03B9}', HGK_SMOOTH ),
+    /* 1F31 */ ( '\u{03B9}', HGK_ROUGH ),
+    /* 1F32 */ ( '\u{03B9}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F33 */ ( '\u{03B9}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F34 */ ( '\u{03B9}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F35 */ ( '\u{03B9}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F36 */ ( '\u{03B9}', HGK_SMOOTH | HGK_CIRCUMFLEX ),
+    /* 1F37 */ ( '\u{03B9}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F38 */ ( '\u{0399}', HGK_SMOOTH ),
+    /* 1F39 */ ( '\u{0399}', HGK_ROUGH ),
+    /* 1F3A */ ( '\u{0399}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F3B */ ( '\u{0399}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F3C */ ( '\u{0399}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F3D */ ( '\u{0399}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F3E */ ( '\u{0399}', HGK_SMOOTH | HGK_CIRCUMFLEX ),
+    /* 1F3F */ ( '\u{0399}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F40 */ ( '\u{03BF}', HGK_SMOOTH ),
+    /* 1F41 */ ( '\u{03BF}', HGK_ROUGH ),
+    /* 1F42 */ ( '\u{03BF}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F43 */ ( '\u{03BF}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F44 */ ( '\u{03BF}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F45 */ ( '\u{03BF}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F46 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F47 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F48 */ ( '\u{039F}', HGK_SMOOTH ),
+    /* 1F49 */ ( '\u{039F}', HGK_ROUGH ),
+    /* 1F4A */ ( '\u{039F}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F4B */ ( '\u{039F}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F4C */ ( '\u{039F}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F4D */ ( '\u{039F}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F4E */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F4F */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F50 */ ( '\u{03C5}', HGK_SMOOTH ),
+    /* 1F51 */ ( '\u{03C5}', HGK_ROUGH ),
+    /* 1F52 */ ( '\u{03C5}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F53 */ ( '\u{03C5}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F54 */ ( '\u{03C5}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F55 */ ( '\u{03C5}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F56 */ ( '\u{03C5}', HGK_SMOOTH | HGK_CIRCUMFLEX ),
+    /* 1F57 */ ( '\u{03C5}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F58 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F59 */ ( '\u{03A5}', HGK_ROUGH ),
+    /* 1F5A */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F5B */ ( '\u{03A5}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F5C */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F5D */ ( '\u{03A5}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F5E */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F5F */ ( '\u{03A5}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F60 */ ( '\u{03C9}', HGK_SMOOTH ),
+    /* 1F61 */ ( '\u{03C9}', HGK_ROUGH ),
+    /* 1F62 */ ( '\u{03C9}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F63 */ ( '\u{03C9}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F64 */ ( '\u{03C9}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F65 */ ( '\u{03C9}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F66 */ ( '\u{03C9}', HGK_SMOOTH | HGK_CIRCUMFLEX ),
+    /* 1F67 */ ( '\u{03C9}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F68 */ ( '\u{03A9}', HGK_SMOOTH ),
+    /* 1F69 */ ( '\u{03A9}', HGK_ROUGH ),
+    /* 1F6A */ ( '\u{03A9}', HGK_SMOOTH | HGK_GRAVE ),
+    /* 1F6B */ ( '\u{03A9}', HGK_ROUGH | HGK_GRAVE ),
+    /* 1F6C */ ( '\u{03A9}', HGK_SMOOTH | HGK_ACUTE ),
+    /* 1F6D */ ( '\u{03A9}', HGK_ROUGH | HGK_ACUTE ),
+    /* 1F6E */ ( '\u{03A9}', HGK_SMOOTH | HGK_CIRCUMFLEX ),
+    /* 1F6F */ ( '\u{03A9}', HGK_ROUGH | HGK_CIRCUMFLEX ),
+    /* 1F70 */ ( '\u{03B1}', HGK_GRAVE ),
+    /* 1F71 */ ( '\u{03B1}', HGK_ACUTE ),
+    /* 1F72 */ ( '\u{03B5}', HGK_GRAVE ),
+    /* 1F73 */ ( '\u{03B5}', HGK_ACUTE ),
+    /* 1F74 */ ( '\u{03B7}', HGK_GRAVE ),
+    /* 1F75 */ ( '\u{03B7}', HGK_ACUTE ),
+    /* 1F76 */ ( '\u{03B9}', HGK_GRAVE ),
+    /* 1F77 */ ( '\u{03B9}', HGK_ACUTE ),
+    /* 1F78 */ ( '\u{03BF}', HGK_GRAVE ),
+    /* 1F79 */ ( '\u{03BF}', HGK_ACUTE ),
+    /* 1F7A */ ( '\u{03C5}', HGK_GRAVE ),
+    /* 1F7B */ ( '\u{03C5}', HGK_ACUTE ),
+    /* 1F7C */ ( '\u{03C9}', HGK_GRAVE ),
+    /* 1F7D */ ( '\u{03C9}', HGK_ACUTE ),
+    /* 1F7E */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F7F */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1F80 */ ( '\u{03B1}', HGK_SMOOTH | HGK_IOTA_SUBSCRIPT ),
+    /* 1F81 */ ( '\u{03B1}', HGK_ROUGH | HGK_IOTA_SUBSCRIPT ),
+    /* 1F82 */ ( '\u{03B1}', HGK_SMOOTH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F83 */ ( '\u{03B1}', HGK_ROUGH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F84 */ ( '\u{03B1}', HGK_SMOOTH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F85 */ ( '\u{03B1}', HGK_ROUGH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F86 */ ( '\u{03B1}', HGK_SMOOTH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1F87 */ ( '\u{03B1}', HGK_ROUGH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1F88 */ ( '\u{0391}', HGK_SMOOTH | HGK_IOTA_SUBSCRIPT ),
+    /* 1F89 */ ( '\u{0391}', HGK_ROUGH | HGK_IOTA_SUBSCRIPT ),
+    /* 1F8A */ ( '\u{0391}', HGK_SMOOTH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F8B */ ( '\u{0391}', HGK_ROUGH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F8C */ ( '\u{0391}', HGK_SMOOTH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F8D */ ( '\u{0391}', HGK_ROUGH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F8E */ ( '\u{0391}', HGK_SMOOTH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1F8F */ ( '\u{0391}', HGK_ROUGH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1F90 */ ( '\u{03B7}', HGK_SMOOTH | HGK_IOTA_SUBSCRIPT ),
+    /* 1F91 */ ( '\u{03B7}', HGK_ROUGH | HGK_IOTA_SUBSCRIPT ),
+    /* 1F92 */ ( '\u{03B7}', HGK_SMOOTH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F93 */ ( '\u{03B7}', HGK_ROUGH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F94 */ ( '\u{03B7}', HGK_SMOOTH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F95 */ ( '\u{03B7}', HGK_ROUGH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F96 */ ( '\u{03B7}', HGK_SMOOTH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1F97 */ ( '\u{03B7}', HGK_ROUGH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1F98 */ ( '\u{0397}', HGK_SMOOTH | HGK_IOTA_SUBSCRIPT ),
+    /* 1F99 */ ( '\u{0397}', HGK_ROUGH | HGK_IOTA_SUBSCRIPT ),
+    /* 1F9A */ ( '\u{0397}', HGK_SMOOTH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F9B */ ( '\u{0397}', HGK_ROUGH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F9C */ ( '\u{0397}', HGK_SMOOTH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F9D */ ( '\u{0397}', HGK_ROUGH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1F9E */ ( '\u{0397}', HGK_SMOOTH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1F9F */ ( '\u{0397}', HGK_ROUGH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA0 */ ( '\u{03C9}', HGK_SMOOTH | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA1 */ ( '\u{03C9}', HGK_ROUGH | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA2 */ ( '\u{03C9}', HGK_SMOOTH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA3 */ ( '\u{03C9}', HGK_ROUGH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA4 */ ( '\u{03C9}', HGK_SMOOTH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA5 */ ( '\u{03C9}', HGK_ROUGH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA6 */ ( '\u{03C9}', HGK_SMOOTH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA7 */ ( '\u{03C9}', HGK_ROUGH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA8 */ ( '\u{03A9}', HGK_SMOOTH | HGK_IOTA_SUBSCRIPT ),
+    /* 1FA9 */ ( '\u{03A9}', HGK_ROUGH | HGK_IOTA_SUBSCRIPT ),
+    /* 1FAA */ ( '\u{03A9}', HGK_SMOOTH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FAB */ ( '\u{03A9}', HGK_ROUGH | HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FAC */ ( '\u{03A9}', HGK_SMOOTH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FAD */ ( '\u{03A9}', HGK_ROUGH | HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FAE */ ( '\u{03A9}', HGK_SMOOTH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1FAF */ ( '\u{03A9}', HGK_ROUGH | HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1FB0 */ ( '\u{03B1}', HGK_BREVE ),
+    /* 1FB1 */ ( '\u{03B1}', HGK_MACRON ),
+    /* 1FB2 */ ( '\u{03B1}', HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FB3 */ ( '\u{03B1}', HGK_IOTA_SUBSCRIPT ),
+    /* 1FB4 */ ( '\u{03B1}', HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FB5 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FB6 */ ( '\u{03B1}', HGK_CIRCUMFLEX ),
+    /* 1FB7 */ ( '\u{03B1}', HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1FB8 */ ( '\u{0391}', HGK_BREVE ),
+    /* 1FB9 */ ( '\u{0391}', HGK_MACRON ),
+    /* 1FBA */ ( '\u{0391}', HGK_GRAVE ),
+    /* 1FBB */ ( '\u{0391}', HGK_ACUTE ),
+    /* 1FBC */ ( '\u{0391}', HGK_IOTA_SUBSCRIPT ),
+    /* 1FBD */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FBE */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FBF */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FC0 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FC1 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FC2 */ ( '\u{03B7}', HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FC3 */ ( '\u{03B7}', HGK_IOTA_SUBSCRIPT ),
+    /* 1FC4 */ ( '\u{03B7}', HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FC5 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FC6 */ ( '\u{03B7}', HGK_CIRCUMFLEX ),
+    /* 1FC7 */ ( '\u{03B7}', HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1FC8 */ ( '\u{0395}', HGK_GRAVE ),
+    /* 1FC9 */ ( '\u{0395}', HGK_ACUTE ),
+    /* 1FCA */ ( '\u{0397}', HGK_GRAVE ),
+    /* 1FCB */ ( '\u{0397}', HGK_ACUTE ),
+    /* 1FCC */ ( '\u{0397}', HGK_IOTA_SUBSCRIPT ),
+    /* 1FCD */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FCE */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FCF */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FD0 */ ( '\u{03B9}', HGK_BREVE ),
+    /* 1FD1 */ ( '\u{03B9}', HGK_MACRON ),
+    /* 1FD2 */ ( '\u{03B9}', HGK_GRAVE | HGK_DIAERESIS ),
+    /* 1FD3 */ ( '\u{03B9}', HGK_ACUTE | HGK_DIAERESIS ),
+    /* 1FD4 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FD5 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FD6 */ ( '\u{03B9}', HGK_CIRCUMFLEX ),
+    /* 1FD7 */ ( '\u{03B9}', HGK_CIRCUMFLEX | HGK_DIAERESIS ),
+    /* 1FD8 */ ( '\u{0399}', HGK_BREVE ),
+    /* 1FD9 */ ( '\u{0399}', HGK_MACRON ),
+    /* 1FDA */ ( '\u{0399}', HGK_GRAVE ),
+    /* 1FDB */ ( '\u{0399}', HGK_ACUTE ),
+    /* 1FDC */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FDD */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FDE */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FDF */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FE0 */ ( '\u{03C5}', HGK_BREVE ),
+    /* 1FE1 */ ( '\u{03C5}', HGK_MACRON ),
+    /* 1FE2 */ ( '\u{03C5}', HGK_GRAVE | HGK_DIAERESIS ),
+    /* 1FE3 */ ( '\u{03C5}', HGK_ACUTE | HGK_DIAERESIS ),
+    /* 1FE4 */ ( '\u{03C1}', HGK_SMOOTH ),
+    /* 1FE5 */ ( '\u{03C1}', HGK_ROUGH ),
+    /* 1FE6 */ ( '\u{03C5}', HGK_CIRCUMFLEX ),
+    /* 1FE7 */ ( '\u{03C5}', HGK_CIRCUMFLEX | HGK_DIAERESIS ),
+    /* 1FE8 */ ( '\u{03A5}', HGK_BREVE ),
+    /* 1FE9 */ ( '\u{03A5}', HGK_MACRON ),
+    /* 1FEA */ ( '\u{03A5}', HGK_GRAVE ),
+    /* 1FEB */ ( '\u{03A5}', HGK_ACUTE ),
+    /* 1FEC */ ( '\u{03A1}', HGK_ROUGH ),
+    /* 1FED */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FEE */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FEF */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FF0 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FF1 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FF2 */ ( '\u{03C9}', HGK_GRAVE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FF3 */ ( '\u{03C9}', HGK_IOTA_SUBSCRIPT ),
+    /* 1FF4 */ ( '\u{03C9}', HGK_ACUTE | HGK_IOTA_SUBSCRIPT ),
+    /* 1FF5 */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FF6 */ ( '\u{03C9}', HGK_CIRCUMFLEX ),
+    /* 1FF7 */ ( '\u{03C9}', HGK_CIRCUMFLEX | HGK_IOTA_SUBSCRIPT ),
+    /* 1FF8 */ ( '\u{039F}', HGK_GRAVE ),
+    /* 1FF9 */ ( '\u{039F}', HGK_ACUTE ),
+    /* 1FFA */ ( '\u{03A9}', HGK_GRAVE ),
+    /* 1FFB */ ( '\u{03A9}', HGK_ACUTE ),
+    /* 1FFC */ ( '\u{03A9}', HGK_IOTA_SUBSCRIPT ),
+    /* 1FFD */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FFE */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+    /* 1FFF */ ( '\u{0000}', HGK_NO_DIACRITICS ),
+];
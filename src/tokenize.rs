@@ -0,0 +1,168 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HgkTokenKind {
+    Word,
+    Numeral,     //an alphabetic numeral, e.g. ρκδʹ, or arabic digits
+    Punctuation,
+    NonGreek
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HgkToken<'a> {
+    pub kind: HgkTokenKind,
+    pub range: Range<usize>,
+    pub text: &'a str,
+    pub elided: bool, //ends with an apostrophe, e.g. δ’
+    pub crasis: bool  //has a koronis, e.g. κἀγώ
+}
+
+impl<'a> HgkToken<'a> {
+    /// The greek letters of the token, without brackets, apostrophes or keraiai.
+    pub fn letters(&self) -> impl Iterator<Item = HGKLetter> + 'a {
+        self.text.gkletters().filter(|l| is_letter(l.letter))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Class {
+    Letter,
+    Keraia,
+    Bracket,
+    Apostrophe,
+    Space,
+    Digit,
+    Other,       //non-greek letters
+    Punctuation
+}
+
+fn is_letter(c:char) -> bool {
+    hgk_is_greek_letter(c) && c != HGK_KERAIA && c != HGK_LOWER_KERAIA
+}
+
+fn classify(c:char) -> Class {
+    match c {
+        _ if is_letter(c) => Class::Letter,
+        HGK_KERAIA | HGK_LOWER_KERAIA | '\u{02B9}' => Class::Keraia,
+        '[' | ']' | '(' | ')' | '{' | '}' | '⟨' | '⟩' | '〈' | '〉' | '⟦' | '⟧' | '⸢' | '⸣' | '⸤' | '⸥' => Class::Bracket,
+        '\u{2019}' | '\u{1FBD}' | '\u{02BC}' | '\'' => Class::Apostrophe,
+        _ if c.is_whitespace() => Class::Space,
+        _ if c.is_ascii_digit() => Class::Digit,
+        _ if c.is_alphabetic() => Class::Other,
+        _ => Class::Punctuation
+    }
+}
+
+//a breathing inside a word is a koronis, except on ρ or the second vowel of an initial diphthong
+pub(crate) fn hgk_has_koronis(letters:&[HGKLetter]) -> bool {
+    let breathings = Diacritics::ROUGH | Diacritics::SMOOTH;
+    letters.iter().enumerate().skip(1).any(|(i, l)| {
+        l.diacritics.intersects(breathings) && !matches!(l.letter, 'ρ' | 'Ρ')
+            && !(i == 1 && hgk_is_diphthong(letters[0].letter, l.letter) && !l.diacritics.contains(Diacritics::DIAERESIS))
+    })
+}
+
+/// Split text into tokens.  Whitespace is not returned.
+///
+/// Editorial brackets between letters are part of the word, e.g. λό[γο]ς, and otherwise are
+/// punctuation.  An apostrophe after a consonant marks elision and is part of the word; after
+/// a vowel it is a quotation mark.  A word followed by a keraia, or a lower keraia followed by
+/// letters, is a numeral if it parses as one.
+pub fn hgk_tokenize(text:&str) -> Vec<HgkToken<'_>> {
+    let units = text.gkletter_spans().map(|(r, l)| (r, classify(l.letter), l)).collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let token = |kind, range:Range<usize>| HgkToken { kind, text: &text[range.clone()], range, elided: false, crasis: false };
+
+    while i < units.len() {
+        let start = units[i].0.start;
+        match units[i].1 {
+            Class::Space => i += 1,
+            Class::Letter | Class::Keraia if units[i].1 == Class::Letter || units.get(i + 1).is_some_and(|u| u.1 == Class::Letter) => {
+                let mut end = i + 1;
+                loop {
+                    match units.get(end).map(|u| u.1) {
+                        Some(Class::Letter) => end += 1,
+                        Some(Class::Bracket) => {
+                            let after = units[end..].iter().position(|u| u.1 != Class::Bracket).map(|p| end + p);
+                            match after {
+                                Some(a) if units[a].1 == Class::Letter => end = a,
+                                _ => break
+                            }
+                        },
+                        _ => break
+                    }
+                }
+                let letters = units[i..end].iter().filter(|u| u.1 == Class::Letter).map(|u| u.2).collect::<Vec<_>>();
+                let last = letters.last().map_or('\u{0000}', |l| l.letter);
+
+                let mut kind = HgkTokenKind::Word;
+                let mut elided = false;
+                match units.get(end).map(|u| u.1) {
+                    Some(Class::Keraia) if parse_greek_numeral(&text[start..units[end].0.end]).is_some() => {
+                        kind = HgkTokenKind::Numeral;
+                        end += 1;
+                    },
+                    Some(Class::Apostrophe) if !last.is_greek_vowel() => {
+                        elided = true;
+                        end += 1;
+                    },
+                    _ => {}
+                }
+                //͵α without a final keraia
+                if units[i].1 == Class::Keraia && parse_greek_numeral(&text[start..units[end - 1].0.end]).is_some() {
+                    kind = HgkTokenKind::Numeral;
+                }
+
+                let range = start..units[end - 1].0.end;
+                tokens.push(HgkToken { kind, text: &text[range.clone()], range, elided, crasis: kind == HgkTokenKind::Word && hgk_has_koronis(&letters) });
+                i = end;
+            },
+            Class::Digit | Class::Other => {
+                let class = units[i].1;
+                let end = units[i..].iter().position(|u| u.1 != class).map_or(units.len(), |p| i + p);
+                let kind = if class == Class::Digit { HgkTokenKind::Numeral } else { HgkTokenKind::NonGreek };
+                tokens.push(token(kind, start..units[end - 1].0.end));
+                i = end;
+            },
+            _ => {
+                tokens.push(token(HgkTokenKind::Punctuation, units[i].0.clone()));
+                i += 1;
+            }
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(text:&str) -> Vec<(HgkTokenKind, &str)> {
+        hgk_tokenize(text).into_iter().map(|t| (t.kind, t.text)).collect()
+    }
+
+    #[test]
+    fn tokenize() {
+        use HgkTokenKind::*;
+        assert_eq!(summary("ἀλλ’ ἐγὼ δ᾽ οὐ· τί λέγεις;"), vec![(Word, "ἀλλ’"), (Word, "ἐγὼ"), (Word, "δ᾽"), (Word, "οὐ"), (Punctuation, "·"),
+            (Word, "τί"), (Word, "λέγεις"), (Punctuation, ";")]);
+        assert_eq!(summary("τί\u{037E} ἔφη\u{0387}"), vec![(Word, "τί"), (Punctuation, "\u{037E}"), (Word, "ἔφη"), (Punctuation, "\u{0387}")]);
+        assert_eq!(summary("[καὶ] λό[γο]ς ⟨ὁ⟩"), vec![(Punctuation, "["), (Word, "καὶ"), (Punctuation, "]"), (Word, "λό[γο]ς"),
+            (Punctuation, "⟨"), (Word, "ὁ"), (Punctuation, "⟩")]);
+        assert_eq!(summary("ρκδʹ ͵βκγʹ ἔτει 1821 Homer"), vec![(Numeral, "ρκδʹ"), (Numeral, "͵βκγʹ"), (Word, "ἔτει"), (Numeral, "1821"), (NonGreek, "Homer")]);
+
+        //an apostrophe after a vowel is a quotation mark
+        assert_eq!(summary("‘λόγοι’"), vec![(Punctuation, "‘"), (Word, "λόγοι"), (Punctuation, "’")]);
+
+        let tokens = hgk_tokenize("κἀγὼ τοῦτ’ εἶπον αὐτῷ ἄῤῥητα");
+        assert_eq!(tokens.iter().map(|t| t.crasis).collect::<Vec<_>>(), vec![true, false, false, false, false]);
+        assert_eq!(tokens.iter().map(|t| t.elided).collect::<Vec<_>>(), vec![false, true, false, false, false]);
+        assert_eq!(tokens[1].range, 11..23);
+        assert_eq!(tokens[1].letters().map(|l| l.letter).collect::<String>(), "τουτ");
+        assert_eq!(tokens[0].letters().nth(1), Some(HGKLetter::new('α', Diacritics::SMOOTH)));
+    }
+}